@@ -47,6 +47,9 @@ impl SysBackend for WebBackend {
     fn any(&self) -> &dyn Any {
         self
     }
+    fn name(&self) -> &str {
+        "web"
+    }
     fn print_str_stdout(&self, s: &str) -> Result<(), String> {
         let mut stdout = self.stdout.lock().unwrap();
         let mut lines = s.lines();
@@ -120,6 +123,23 @@ impl SysBackend for WebBackend {
         while (instant::now() - start) / 1000.0 < seconds {}
         Ok(())
     }
+    fn cpu_time(&self) -> Result<f64, String> {
+        Ok(instant::now() / 1000.0)
+    }
+    fn secure_random(&self, n: usize) -> Result<Vec<u8>, String> {
+        let mut bytes = vec![0; n];
+        // getRandomValues fills at most 65536 bytes per call
+        for chunk in bytes.chunks_mut(65536) {
+            window()
+                .crypto()
+                .and_then(|crypto| crypto.get_random_values_with_u8_array(chunk))
+                .map_err(|e| format!("Failed to get random values: {e:?}"))?;
+        }
+        Ok(bytes)
+    }
+    fn system_memory(&self) -> Result<(u64, u64), String> {
+        Ok((0, 0))
+    }
     fn spawn(
         &self,
         env: Uiua,
@@ -1,12 +1,21 @@
 //! Algorithms for dyadic array operations
 
-use std::{borrow::Cow, cmp::Ordering, iter::repeat, mem::take, sync::Arc};
+use std::{
+    any::Any,
+    borrow::Cow,
+    cmp::Ordering,
+    collections::HashMap,
+    hash::{Hash, Hasher},
+    iter::repeat,
+    mem::take,
+    sync::Arc,
+};
 
 use tinyvec::tiny_vec;
 
 use crate::{
-    algorithm::max_shape, array::*, cowslice::CowSlice, function::Function, value::Value, Uiua,
-    UiuaResult,
+    algorithm::max_shape, array::*, complex::Complex, cowslice::CowSlice, function::Function,
+    value::Value, Uiua, UiuaResult,
 };
 
 use super::{op2_bytes_retry_fill, op_bytes_ref_retry_fill, op_bytes_retry_fill, FillContext};
@@ -119,8 +128,13 @@ impl Value {
                 |a, b| Ok(a.join_impl(b, ctx)?.into()),
             )?,
             (Value::Char(a), Value::Char(b)) => a.join_impl(b, ctx)?.into(),
+            (Value::Complex(a), Value::Complex(b)) => a.join_impl(b, ctx)?.into(),
             (Value::Byte(a), Value::Num(b)) => a.convert().join_impl(b, ctx)?.into(),
             (Value::Num(a), Value::Byte(b)) => a.join_impl(b.convert(), ctx)?.into(),
+            (Value::Num(a), Value::Complex(b)) => a.convert().join_impl(b, ctx)?.into(),
+            (Value::Complex(a), Value::Num(b)) => a.join_impl(b.convert(), ctx)?.into(),
+            (Value::Byte(a), Value::Complex(b)) => a.convert().join_impl(b, ctx)?.into(),
+            (Value::Complex(a), Value::Byte(b)) => a.join_impl(b.convert(), ctx)?.into(),
             (a, b) => a.coerce_to_functions(
                 b,
                 ctx,
@@ -147,12 +161,25 @@ impl Value {
                 )?;
             }
             (Value::Char(a), Value::Char(b)) => a.append(b, ctx)?,
+            (Value::Complex(a), Value::Complex(b)) => a.append(b, ctx)?,
             (Value::Byte(a), Value::Num(b)) => {
                 let mut a = a.convert_ref();
                 a.append(b, ctx)?;
                 *self = a.into();
             }
             (Value::Num(a), Value::Byte(b)) => a.append(b.convert(), ctx)?,
+            (Value::Num(a), Value::Complex(b)) => {
+                let mut a = a.convert_ref();
+                a.append(b, ctx)?;
+                *self = a.into();
+            }
+            (Value::Byte(a), Value::Complex(b)) => {
+                let mut a = a.convert_ref();
+                a.append(b, ctx)?;
+                *self = a.into();
+            }
+            (Value::Complex(a), Value::Num(b)) => a.append(b.convert(), ctx)?,
+            (Value::Complex(a), Value::Byte(b)) => a.append(b.convert(), ctx)?,
             (a, b) => {
                 *self = a.clone().coerce_to_functions(
                     b,
@@ -169,6 +196,14 @@ impl Value {
     }
 }
 
+fn join_shape_error<T: ArrayValue>(a: &Array<T>, b: &Array<T>) -> String {
+    format!(
+        "Cannot join array of shape {} with array of shape {}",
+        a.format_shape(),
+        b.format_shape()
+    )
+}
+
 impl<T: ArrayValue> Array<T> {
     pub fn join(self, other: Self, env: &Uiua) -> UiuaResult<Self> {
         self.join_impl(other, env)
@@ -187,19 +222,8 @@ impl<T: ArrayValue> Array<T> {
                     other.fill_to_shape(&target_shape, fill);
                     target_shape
                 } else {
-                    if other.rank() - self.rank() > 1 {
-                        return Err(C::fill_error(ctx.error(format!(
-                            "Cannot join rank {} array with rank {} array",
-                            self.rank(),
-                            other.rank()
-                        ))));
-                    }
-                    if self.shape() != &other.shape()[1..] {
-                        return Err(C::fill_error(ctx.error(format!(
-                            "Cannot join arrays of shapes {} and {}",
-                            self.format_shape(),
-                            other.format_shape()
-                        ))));
+                    if other.rank() - self.rank() > 1 || self.shape() != &other.shape()[1..] {
+                        return Err(C::fill_error(ctx.error(join_shape_error(&self, &other))));
                     }
                     other.shape
                 };
@@ -209,6 +233,11 @@ impl<T: ArrayValue> Array<T> {
                 self
             }
             Ordering::Greater => {
+                if ctx.fill::<T>().is_none()
+                    && (self.rank() - other.rank() > 1 || &self.shape()[1..] != other.shape())
+                {
+                    return Err(C::fill_error(ctx.error(join_shape_error(&self, &other))));
+                }
                 self.append(other, ctx)?;
                 self
             }
@@ -227,11 +256,7 @@ impl<T: ArrayValue> Array<T> {
                             array.fill_to_shape(&new_shape, fill);
                         }
                     } else if self.shape[1..] != other.shape[1..] {
-                        return Err(C::fill_error(ctx.error(format!(
-                            "Cannot join arrays of shapes {} and {}",
-                            self.format_shape(),
-                            other.format_shape()
-                        ))));
+                        return Err(C::fill_error(ctx.error(join_shape_error(&self, &other))));
                     }
                     self.data.extend(other.data);
                     self.shape[0] += other.shape[0];
@@ -242,7 +267,11 @@ impl<T: ArrayValue> Array<T> {
         res.validate_shape();
         Ok(res)
     }
-    fn append<C: FillContext>(&mut self, mut other: Self, ctx: C) -> Result<(), C::Error> {
+    pub(crate) fn append<C: FillContext>(
+        &mut self,
+        mut other: Self,
+        ctx: C,
+    ) -> Result<(), C::Error> {
         let target_shape = if let Some(fill) = ctx.fill::<T>() {
             while self.rank() <= other.rank() {
                 self.shape.push(1);
@@ -281,6 +310,14 @@ impl Value {
         self.couple_impl(other, env)?;
         Ok(self)
     }
+    /// Couple two arrays if their shapes are equal, otherwise join them
+    pub fn join_or_couple(self, other: Self, env: &Uiua) -> UiuaResult<Self> {
+        if self.shape() == other.shape() {
+            self.couple(other, env)
+        } else {
+            self.join(other, env)
+        }
+    }
     pub fn couple_infallible(mut self, other: Self) -> Self {
         self.couple_impl(other, ()).unwrap();
         self
@@ -307,8 +344,21 @@ impl Value {
                 )?
             }
             (Value::Char(a), Value::Char(b)) => a.couple_impl(b, ctx)?,
+            (Value::Complex(a), Value::Complex(b)) => a.couple_impl(b, ctx)?,
             (Value::Func(a), Value::Func(b)) => a.couple_impl(b, ctx)?,
             (Value::Num(a), Value::Byte(b)) => a.couple_impl(b.convert(), ctx)?,
+            (Value::Complex(a), Value::Num(b)) => a.couple_impl(b.convert(), ctx)?,
+            (Value::Complex(a), Value::Byte(b)) => a.couple_impl(b.convert(), ctx)?,
+            (Value::Num(a), Value::Complex(b)) => {
+                let mut a = a.convert_ref();
+                a.couple_impl(b, ctx)?;
+                *self = a.into();
+            }
+            (Value::Byte(a), Value::Complex(b)) => {
+                let mut a = a.convert_ref();
+                a.couple_impl(b, ctx)?;
+                *self = a.into();
+            }
             (Value::Byte(a), Value::Num(b)) => {
                 let mut a = a.convert_ref();
                 a.couple_impl(b, ctx)?;
@@ -332,6 +382,7 @@ impl Value {
         match self {
             Value::Num(a) => a.uncouple(env).map(|(a, b)| (a.into(), b.into())),
             Value::Byte(a) => a.uncouple(env).map(|(a, b)| (a.into(), b.into())),
+            Value::Complex(a) => a.uncouple(env).map(|(a, b)| (a.into(), b.into())),
             Value::Char(a) => a.uncouple(env).map(|(a, b)| (a.into(), b.into())),
             Value::Func(a) => a.uncouple(env).map(|(a, b)| (a.into(), b.into())),
         }
@@ -421,6 +472,26 @@ impl Value {
     }
 }
 
+impl Value {
+    /// Stack arrays along a new first axis
+    ///
+    /// This is like [`Value::couple`] for any number of arrays.
+    pub fn stack(arrays: Vec<Value>, env: &Uiua) -> UiuaResult<Self> {
+        if let Some(first) = arrays.first() {
+            if env.num_fill().is_none() && env.char_fill().is_none() && env.func_fill().is_none() {
+                if let Some(mismatch) = arrays.iter().find(|a| a.shape() != first.shape()) {
+                    return Err(env.error(format!(
+                        "Cannot stack arrays with shapes {} and {}",
+                        first.format_shape(),
+                        mismatch.format_shape()
+                    )));
+                }
+            }
+        }
+        Value::from_row_values(arrays, env)
+    }
+}
+
 impl<T: ArrayValue> Array<T> {
     #[track_caller]
     pub fn from_row_arrays(values: impl IntoIterator<Item = Self>, env: &Uiua) -> UiuaResult<Self> {
@@ -457,11 +528,36 @@ impl<T: ArrayValue> Array<T> {
 }
 
 impl Value {
+    /// Split the last axis of an array into two axes
+    pub fn split(&mut self, trailing: &Self, env: &Uiua) -> UiuaResult {
+        let trailing =
+            trailing.as_naturals(env, "Split shape must be a list of natural numbers")?;
+        let &[h, w] = trailing.as_slice() else {
+            return Err(env.error(format!(
+                "Split shape must have 2 elements, but it has {}",
+                trailing.len()
+            )));
+        };
+        let shape = self.shape_mut();
+        let Some(&last) = shape.last() else {
+            return Err(env.error("Cannot split the last axis of a scalar"));
+        };
+        if h * w != last {
+            return Err(env.error(format!(
+                "Cannot split an axis of length {last} into shape {h}×{w}"
+            )));
+        }
+        shape.pop();
+        shape.push(h);
+        shape.push(w);
+        Ok(())
+    }
     pub fn reshape(&mut self, shape: &Self, env: &Uiua) -> UiuaResult {
         if let Ok(n) = shape.as_nat(env, "") {
             match self {
                 Value::Num(a) => a.reshape_scalar(n),
                 Value::Byte(a) => a.reshape_scalar(n),
+                Value::Complex(a) => a.reshape_scalar(n),
                 Value::Char(a) => a.reshape_scalar(n),
                 Value::Func(a) => a.reshape_scalar(n),
             }
@@ -474,6 +570,7 @@ impl Value {
             match self {
                 Value::Num(a) => a.reshape(&target_shape, env),
                 Value::Byte(a) => a.reshape(&target_shape, env),
+                Value::Complex(a) => a.reshape(&target_shape, env),
                 Value::Char(a) => a.reshape(&target_shape, env),
                 Value::Func(a) => a.reshape(&target_shape, env),
             }?
@@ -504,7 +601,17 @@ impl<T: ArrayValue> Array<T> {
                 neg_count += 1;
             }
         }
+        // A 0 dimension makes the length of an inferred dimension ambiguous
+        if neg_count == 1 && dims.contains(&0) {
+            return Err(env.error(
+                "Cannot reshape array with a negative dimension \
+                and a 0 dimension, because the negative one is ambiguous",
+            ));
+        }
         let derive_len = |data_len: usize, other_len: usize| {
+            if data_len == 0 {
+                return 0;
+            }
             (if env.fill::<T>().is_some() {
                 f32::ceil
             } else {
@@ -522,11 +629,6 @@ impl<T: ArrayValue> Array<T> {
                         );
                     }
                     let shape_non_leading_len = dims[1..].iter().product::<isize>() as usize;
-                    if shape_non_leading_len == 0 {
-                        return Err(
-                            env.error("Cannot reshape array with any 0 non-leading dimensions")
-                        );
-                    }
                     let leading_len = derive_len(self.data.len(), shape_non_leading_len);
                     let mut shape = vec![leading_len];
                     shape.extend(dims[1..].iter().map(|&dim| dim as usize));
@@ -539,11 +641,6 @@ impl<T: ArrayValue> Array<T> {
                     }
                     let shape_non_trailing_len =
                         dims.iter().rev().skip(1).product::<isize>() as usize;
-                    if shape_non_trailing_len == 0 {
-                        return Err(
-                            env.error("Cannot reshape array with any 0 non-trailing dimensions")
-                        );
-                    }
                     let trailing_len = derive_len(self.data.len(), shape_non_trailing_len);
                     let mut shape: Vec<usize> = dims.iter().map(|&dim| dim as usize).collect();
                     shape.pop();
@@ -555,9 +652,6 @@ impl<T: ArrayValue> Array<T> {
                     let back = &back[1..];
                     let front_len = front.iter().product::<isize>() as usize;
                     let back_len = back.iter().product::<isize>() as usize;
-                    if front_len == 0 || back_len == 0 {
-                        return Err(env.error("Cannot reshape array with any 0 outer dimensions"));
-                    }
                     let middle_len = derive_len(self.data.len(), front_len * back_len);
                     let mut shape: Vec<usize> = front.iter().map(|&dim| dim as usize).collect();
                     shape.push(middle_len);
@@ -577,6 +671,11 @@ impl<T: ArrayValue> Array<T> {
                 self.data.modify(|data| {
                     data.extend(repeat(fill).take(target_len - start));
                 });
+            } else if self.data.is_empty() {
+                return Err(env.error(format!(
+                    "Cannot reshape empty array to shape {} without a fill value",
+                    FormatShape(&self.shape)
+                )));
             } else {
                 let start = self.data.len();
                 self.data.modify(|data| {
@@ -605,6 +704,7 @@ impl Value {
             match kept {
                 Value::Num(a) => a.scalar_keep(counts[0]).into(),
                 Value::Byte(a) => a.scalar_keep(counts[0]).into(),
+                Value::Complex(a) => a.scalar_keep(counts[0]).into(),
                 Value::Char(a) => a.scalar_keep(counts[0]).into(),
                 Value::Func(a) => a.scalar_keep(counts[0]).into(),
             }
@@ -612,6 +712,7 @@ impl Value {
             match kept {
                 Value::Num(a) => a.list_keep(&counts, env)?.into(),
                 Value::Byte(a) => a.list_keep(&counts, env)?.into(),
+                Value::Complex(a) => a.list_keep(&counts, env)?.into(),
                 Value::Char(a) => a.list_keep(&counts, env)?.into(),
                 Value::Func(a) => a.list_keep(&counts, env)?.into(),
             }
@@ -630,9 +731,14 @@ impl Value {
             (Value::Num(a), Value::Num(b)) => a.unkeep(&counts, b, env)?.into(),
             (Value::Byte(a), Value::Byte(b)) => a.unkeep(&counts, b, env)?.into(),
             (Value::Char(a), Value::Char(b)) => a.unkeep(&counts, b, env)?.into(),
+            (Value::Complex(a), Value::Complex(b)) => a.unkeep(&counts, b, env)?.into(),
             (Value::Func(a), Value::Func(b)) => a.unkeep(&counts, b, env)?.into(),
             (Value::Num(a), Value::Byte(b)) => a.unkeep(&counts, b.convert(), env)?.into(),
             (Value::Byte(a), Value::Num(b)) => a.convert().unkeep(&counts, b, env)?.into(),
+            (Value::Num(a), Value::Complex(b)) => a.convert().unkeep(&counts, b, env)?.into(),
+            (Value::Complex(a), Value::Num(b)) => a.unkeep(&counts, b.convert(), env)?.into(),
+            (Value::Byte(a), Value::Complex(b)) => a.convert().unkeep(&counts, b, env)?.into(),
+            (Value::Complex(a), Value::Byte(b)) => a.unkeep(&counts, b.convert(), env)?.into(),
             (a, b) => a.coerce_to_functions(
                 b,
                 env,
@@ -841,6 +947,7 @@ impl Value {
                 |a| Ok(a.pick_shaped(&index_shape, &index_data, env)?.into()),
                 |a| Ok(a.pick_shaped(&index_shape, &index_data, env)?.into()),
             )?,
+            Value::Complex(a) => Value::Complex(a.pick_shaped(&index_shape, &index_data, env)?),
             Value::Char(a) => Value::Char(a.pick_shaped(&index_shape, &index_data, env)?),
             Value::Func(a) => Value::Func(a.pick_shaped(&index_shape, &index_data, env)?),
         })
@@ -851,9 +958,14 @@ impl Value {
             (Value::Num(a), Value::Num(b)) => a.unpick_impl(&index, b, env)?.into(),
             (Value::Byte(a), Value::Byte(b)) => a.unpick_impl(&index, b, env)?.into(),
             (Value::Char(a), Value::Char(b)) => a.unpick_impl(&index, b, env)?.into(),
+            (Value::Complex(a), Value::Complex(b)) => a.unpick_impl(&index, b, env)?.into(),
             (Value::Func(a), Value::Func(b)) => a.unpick_impl(&index, b, env)?.into(),
             (Value::Num(a), Value::Byte(b)) => a.unpick_impl(&index, b.convert(), env)?.into(),
             (Value::Byte(a), Value::Num(b)) => a.convert().unpick_impl(&index, b, env)?.into(),
+            (Value::Num(a), Value::Complex(b)) => a.convert().unpick_impl(&index, b, env)?.into(),
+            (Value::Complex(a), Value::Num(b)) => a.unpick_impl(&index, b.convert(), env)?.into(),
+            (Value::Byte(a), Value::Complex(b)) => a.convert().unpick_impl(&index, b, env)?.into(),
+            (Value::Complex(a), Value::Byte(b)) => a.unpick_impl(&index, b.convert(), env)?.into(),
             (a, b) => a
                 .coerce_to_functions(
                     b,
@@ -908,20 +1020,20 @@ impl<T: ArrayValue> Array<T> {
         let mut picked = self.data.clone();
         for (d, (&s, &i)) in self.shape.iter().zip(index).enumerate() {
             let row_len: usize = self.shape[d + 1..].iter().product();
-            let s = s as isize;
-            if i >= s || i < -s {
+            let Some(i) = normalize_index(i, s) else {
                 if let Some(fill) = env.fill::<T>() {
                     picked = vec![fill; row_len].into();
                     continue;
                 }
                 return Err(env
                     .error(format!(
-                        "Index {i} is out of bounds of length {s} (dimension {d}) in shape {}",
-                        self.format_shape()
+                        "Index {} is out of bounds of length {s} (dimension {d}) in shape {}, {}",
+                        format_index(i),
+                        self.format_shape(),
+                        valid_indices(s)
                     ))
                     .fill());
-            }
-            let i = if i >= 0 { i as usize } else { (s + i) as usize };
+            };
             let start = i * row_len;
             let end = start + row_len;
             picked = picked.slice(start..end);
@@ -967,6 +1079,7 @@ impl Value {
                 |a| Ok(a.take(&index, env)?.into()),
                 |a| Ok(a.take(&index, env)?.into()),
             )?,
+            Value::Complex(a) => Value::Complex(a.take(&index, env)?),
             Value::Char(a) => Value::Char(a.take(&index, env)?),
             Value::Func(a) => Value::Func(a.take(&index, env)?),
         })
@@ -979,6 +1092,7 @@ impl Value {
         Ok(match from {
             Value::Num(a) => Value::Num(a.drop(&index, env)?),
             Value::Byte(a) => Value::Byte(a.drop(&index, env)?),
+            Value::Complex(a) => Value::Complex(a.drop(&index, env)?),
             Value::Char(a) => Value::Char(a.drop(&index, env)?),
             Value::Func(a) => Value::Func(a.drop(&index, env)?),
         })
@@ -989,9 +1103,22 @@ impl Value {
             (Value::Num(a), Value::Num(b)) => Value::Num(a.untake(&index, b, env)?),
             (Value::Byte(a), Value::Byte(b)) => Value::Byte(a.untake(&index, b, env)?),
             (Value::Char(a), Value::Char(b)) => Value::Char(a.untake(&index, b, env)?),
+            (Value::Complex(a), Value::Complex(b)) => Value::Complex(a.untake(&index, b, env)?),
             (Value::Func(a), Value::Func(b)) => Value::Func(a.untake(&index, b, env)?),
             (Value::Num(a), Value::Byte(b)) => Value::Num(a.untake(&index, b.convert(), env)?),
             (Value::Byte(a), Value::Num(b)) => Value::Num(a.convert().untake(&index, b, env)?),
+            (Value::Num(a), Value::Complex(b)) => {
+                Value::Complex(a.convert().untake(&index, b, env)?)
+            }
+            (Value::Complex(a), Value::Num(b)) => {
+                Value::Complex(a.untake(&index, b.convert(), env)?)
+            }
+            (Value::Byte(a), Value::Complex(b)) => {
+                Value::Complex(a.convert().untake(&index, b, env)?)
+            }
+            (Value::Complex(a), Value::Byte(b)) => {
+                Value::Complex(a.untake(&index, b.convert(), env)?)
+            }
             (a, b) => {
                 return Err(env.error(format!(
                     "Cannot untake {} into {}",
@@ -1007,9 +1134,22 @@ impl Value {
             (Value::Num(a), Value::Num(b)) => Value::Num(a.undrop(&index, b, env)?),
             (Value::Byte(a), Value::Byte(b)) => Value::Byte(a.undrop(&index, b, env)?),
             (Value::Char(a), Value::Char(b)) => Value::Char(a.undrop(&index, b, env)?),
+            (Value::Complex(a), Value::Complex(b)) => Value::Complex(a.undrop(&index, b, env)?),
             (Value::Func(a), Value::Func(b)) => Value::Func(a.undrop(&index, b, env)?),
             (Value::Num(a), Value::Byte(b)) => Value::Num(a.undrop(&index, b.convert(), env)?),
             (Value::Byte(a), Value::Num(b)) => Value::Num(a.convert().undrop(&index, b, env)?),
+            (Value::Num(a), Value::Complex(b)) => {
+                Value::Complex(a.convert().undrop(&index, b, env)?)
+            }
+            (Value::Complex(a), Value::Num(b)) => {
+                Value::Complex(a.undrop(&index, b.convert(), env)?)
+            }
+            (Value::Byte(a), Value::Complex(b)) => {
+                Value::Complex(a.convert().undrop(&index, b, env)?)
+            }
+            (Value::Complex(a), Value::Byte(b)) => {
+                Value::Complex(a.undrop(&index, b.convert(), env)?)
+            }
             (a, b) => {
                 return Err(env.error(format!(
                     "Cannot undrop {} into {}",
@@ -1025,6 +1165,13 @@ impl<T: ArrayValue> Array<T> {
     pub fn take(mut self, index: &[isize], env: &Uiua) -> UiuaResult<Self> {
         Ok(match index {
             [] => self,
+            &[taking] if taking >= 0 && self.rank() > 0 && taking as usize <= self.row_count() => {
+                // Taking a prefix never needs a new buffer
+                self.data.truncate(taking as usize * self.row_len());
+                self.shape[0] = taking as usize;
+                self.validate_shape();
+                self
+            }
             &[taking] => {
                 let row_len = self.row_len();
                 let row_count = self.row_count();
@@ -1259,19 +1406,29 @@ impl<T: ArrayValue> Array<T> {
                     )));
                 }
                 let into_row_count = into.row_count();
-                let mut new_rows = Vec::with_capacity(into_row_count);
+                // Rows that were filled in by the take have nothing to be put back into
+                let filled = abs_untaking.saturating_sub(into_row_count);
+                let mut new_rows = Vec::with_capacity(into_row_count + filled);
                 if untaking >= 0 {
                     for (from, into) in from.rows().zip(into.rows()) {
                         new_rows.push(from.untake(sub_index, into, env)?);
                     }
+                    new_rows.extend(from.rows().skip(into_row_count));
                     new_rows.extend(into.rows().skip(abs_untaking));
                 } else {
                     let start = into_row_count.saturating_sub(abs_untaking);
                     new_rows.extend(into.rows().take(start));
-                    for (from, into) in from.rows().zip(into.rows().skip(start)) {
+                    new_rows.extend(from.rows().take(filled));
+                    for (from, into) in from.rows().skip(filled).zip(into.rows().skip(start)) {
                         new_rows.push(from.untake(sub_index, into, env)?);
                     }
                 }
+                if filled > 0 && new_rows.windows(2).any(|w| w[0].shape != w[1].shape) {
+                    return Err(env.error(
+                        "Attempted to undo take, but the filled rows \
+                        do not fit the shape of the rest of the array",
+                    ));
+                }
                 Array::from_row_arrays(new_rows, env)?
             }
         })
@@ -1305,6 +1462,7 @@ impl Value {
         match &mut rotated {
             Value::Num(a) => a.rotate(&by, env)?,
             Value::Byte(a) => a.rotate(&by, env)?,
+            Value::Complex(a) => a.rotate(&by, env)?,
             Value::Char(a) => a.rotate(&by, env)?,
             Value::Func(a) => a.rotate(&by, env)?,
         }
@@ -1351,6 +1509,60 @@ fn rotate<T>(by: &[isize], shape: &[usize], data: &mut [T]) {
     }
 }
 
+impl Value {
+    pub fn shift(&self, shifted: Self, env: &Uiua) -> UiuaResult<Self> {
+        let by = self.as_indices(env, "Shift amount must be a list of integers")?;
+        Ok(match shifted {
+            Value::Num(a) => a.shift(&by, env.num_fill().unwrap_or(0.0), env)?.into(),
+            Value::Byte(a) => match (env.byte_fill(), env.num_fill()) {
+                (Some(fill), _) => a.shift(&by, fill, env)?.into(),
+                (None, Some(fill)) => a.convert::<f64>().shift(&by, fill, env)?.into(),
+                (None, None) => a.shift(&by, 0, env)?.into(),
+            },
+            Value::Complex(a) => {
+                let fill = env.complex_fill().unwrap_or(Complex::ZERO);
+                a.shift(&by, fill, env)?.into()
+            }
+            Value::Char(a) => a.shift(&by, env.char_fill().unwrap_or(' '), env)?.into(),
+            Value::Func(a) => {
+                let fill = env
+                    .func_fill()
+                    .ok_or_else(|| env.error("Cannot shift a function array without a fill"))?;
+                a.shift(&by, fill, env)?.into()
+            }
+        })
+    }
+}
+
+impl<T: ArrayValue> Array<T> {
+    pub fn shift(&self, by: &[isize], fill: T, env: &Uiua) -> UiuaResult<Self> {
+        if by.len() > self.rank() {
+            return Err(env.error(format!(
+                "Cannot shift rank {} array with index of length {}",
+                self.rank(),
+                by.len()
+            )));
+        }
+        let mut index = vec![0; self.rank()];
+        let mut data = Vec::with_capacity(self.flat_len());
+        for i in 0..self.flat_len() {
+            data_index_to_shape_index(i, &self.shape, &mut index);
+            // Find where this element is shifted from
+            let mut in_bounds = true;
+            for (i, &b) in index.iter_mut().zip(by) {
+                let from = *i as isize - b;
+                in_bounds &= from >= 0;
+                *i = from.max(0) as usize;
+            }
+            let from = in_bounds
+                .then(|| shape_index_to_data_index(&index, &self.shape))
+                .flatten();
+            data.push(from.map_or_else(|| fill.clone(), |from| self.data[from].clone()));
+        }
+        Ok(Array::new(self.shape.clone(), data))
+    }
+}
+
 impl Value {
     fn as_index_array<'a>(&'a self, env: &Uiua) -> UiuaResult<(&'a [usize], Vec<isize>)> {
         let mut indices = Vec::with_capacity(self.flat_len());
@@ -1388,13 +1600,18 @@ impl Value {
                 |a| Ok(a.select_impl(indices_shape, &indices, env)?.into()),
                 |a| Ok(a.select_impl(indices_shape, &indices, env)?.into()),
             )?,
+            Value::Complex(a) => a.select_impl(indices_shape, &indices, env)?.into(),
             Value::Char(a) => a.select_impl(indices_shape, &indices, env)?.into(),
             Value::Func(a) => a.select_impl(indices_shape, &indices, env)?.into(),
         })
     }
     pub fn unselect(self, index: Self, into: Self, env: &Uiua) -> UiuaResult<Self> {
         let (ind_shape, ind) = index.as_index_array(env)?;
-        let mut sorted_indices = ind.clone();
+        // Negative indices are normalized so that they match their positive counterparts
+        let row_count = into.row_count();
+        let mut sorted_indices: Vec<_> = (ind.iter())
+            .map(|&i| normalize_index(i, row_count).map_or(i, |i| i as isize))
+            .collect();
         sorted_indices.sort();
         if sorted_indices.windows(2).any(|win| win[0] == win[1]) {
             return Err(env.error("Cannot undo selection with duplicate indices"));
@@ -1403,6 +1620,9 @@ impl Value {
             (Value::Num(a), Value::Num(b)) => a.unselect_impl(ind_shape, &ind, b, env)?.into(),
             (Value::Byte(a), Value::Byte(b)) => a.unselect_impl(ind_shape, &ind, b, env)?.into(),
             (Value::Char(a), Value::Char(b)) => a.unselect_impl(ind_shape, &ind, b, env)?.into(),
+            (Value::Complex(a), Value::Complex(b)) => {
+                a.unselect_impl(ind_shape, &ind, b, env)?.into()
+            }
             (Value::Func(a), Value::Func(b)) => a.unselect_impl(ind_shape, &ind, b, env)?.into(),
             (Value::Num(a), Value::Byte(b)) => {
                 a.unselect_impl(ind_shape, &ind, b.convert(), env)?.into()
@@ -1410,6 +1630,18 @@ impl Value {
             (Value::Byte(a), Value::Num(b)) => {
                 a.convert().unselect_impl(ind_shape, &ind, b, env)?.into()
             }
+            (Value::Num(a), Value::Complex(b)) => {
+                a.convert().unselect_impl(ind_shape, &ind, b, env)?.into()
+            }
+            (Value::Complex(a), Value::Num(b)) => {
+                a.unselect_impl(ind_shape, &ind, b.convert(), env)?.into()
+            }
+            (Value::Byte(a), Value::Complex(b)) => {
+                a.convert().unselect_impl(ind_shape, &ind, b, env)?.into()
+            }
+            (Value::Complex(a), Value::Byte(b)) => {
+                a.unselect_impl(ind_shape, &ind, b.convert(), env)?.into()
+            }
             (a, b) => {
                 return Err(env.error(format!(
                     "Cannot untake {} into {}",
@@ -1462,36 +1694,19 @@ impl<T: ArrayValue> Array<T> {
         let row_len = self.row_len();
         let row_count = self.row_count();
         for &i in indices {
-            let i = if i >= 0 {
-                let ui = i as usize;
-                if ui >= row_count {
-                    if let Some(fill) = env.fill::<T>() {
-                        selected.extend(repeat(fill).take(row_len));
-                        continue;
-                    }
-                    return Err(env
-                        .error(format!(
-                            "Index {} is out of bounds of length {}",
-                            i, row_count
-                        ))
-                        .fill());
-                }
-                ui
-            } else {
-                let pos_i = (row_count as isize + i) as usize;
-                if pos_i >= row_count {
-                    if let Some(fill) = env.fill::<T>() {
-                        selected.extend(repeat(fill).take(row_len));
-                        continue;
-                    }
-                    return Err(env
-                        .error(format!(
-                            "Index {} is out of bounds of length {}",
-                            i, row_count
-                        ))
-                        .fill());
+            let Some(i) = normalize_index(i, row_count) else {
+                if let Some(fill) = env.fill::<T>() {
+                    selected.extend(repeat(fill).take(row_len));
+                    continue;
                 }
-                pos_i
+                return Err(env
+                    .error(format!(
+                        "Index {} is out of bounds of length {}, {}",
+                        format_index(i),
+                        row_count,
+                        valid_indices(row_count)
+                    ))
+                    .fill());
             };
             let start = i * row_len;
             let end = start + row_len;
@@ -1517,28 +1732,15 @@ impl<T: ArrayValue> Array<T> {
         let into_row_len = into.row_len();
         let into_row_count = into.row_count();
         for (&i, row) in indices.iter().zip(self.rows()) {
-            let i = if i >= 0 {
-                let ui = i as usize;
-                if ui >= into_row_count {
-                    return Err(env
-                        .error(format!(
-                            "Index {} is out of bounds of length {}",
-                            i, into_row_count
-                        ))
-                        .fill());
-                }
-                ui
-            } else {
-                let pos_i = (into_row_count as isize + i) as usize;
-                if pos_i >= into_row_count {
-                    return Err(env
-                        .error(format!(
-                            "Index {} is out of bounds of length {}",
-                            i, into_row_count
-                        ))
-                        .fill());
-                }
-                pos_i
+            let Some(i) = normalize_index(i, into_row_count) else {
+                return Err(env
+                    .error(format!(
+                        "Index {} is out of bounds of length {}, {}",
+                        format_index(i),
+                        into_row_count,
+                        valid_indices(into_row_count)
+                    ))
+                    .fill());
             };
             let start = i * into_row_len;
             let end = start + into_row_len;
@@ -1556,6 +1758,7 @@ impl Value {
         Ok(match from {
             Value::Num(a) => a.windows(&size_spec, env)?.into(),
             Value::Byte(a) => a.windows(&size_spec, env)?.into(),
+            Value::Complex(a) => a.windows(&size_spec, env)?.into(),
             Value::Char(a) => a.windows(&size_spec, env)?.into(),
             Value::Func(a) => a.windows(&size_spec, env)?.into(),
         })
@@ -1642,6 +1845,7 @@ impl Value {
             (Value::Num(a), Value::Num(b)) => a.find(b, env)?.into(),
             (Value::Byte(a), Value::Byte(b)) => a.find(b, env)?.into(),
             (Value::Char(a), Value::Char(b)) => a.find(b, env)?.into(),
+            (Value::Complex(a), Value::Complex(b)) => a.find(b, env)?.into(),
             (Value::Func(a), Value::Func(b)) => a.find(b, env)?.into(),
             (Value::Num(a), Value::Byte(b)) => a.find(&b.clone().convert(), env)?.into(),
             (Value::Byte(a), Value::Num(b)) => a.clone().convert().find(b, env)?.into(),
@@ -1680,6 +1884,9 @@ impl<T: ArrayValue> Array<T> {
             .map(|(a, b)| a + 1 - b)
             .collect();
 
+        // Items of the searched-for array that match the fill value match anything
+        let wildcard = env.fill::<T>();
+
         let mut data = Vec::new();
         let mut corner = vec![0; searched.shape.len()];
         let mut curr = vec![0; searched.shape.len()];
@@ -1707,7 +1914,10 @@ impl<T: ArrayValue> Array<T> {
                 }
                 // Compare the current items in the two arrays
                 let same = if let Some(searched_for) = self.data.get(search_for_index) {
-                    searched.data[searched_index].array_eq(searched_for)
+                    wildcard
+                        .as_ref()
+                        .is_some_and(|wildcard| searched_for.array_eq(wildcard))
+                        || searched.data[searched_index].array_eq(searched_for)
                 } else {
                     false
                 };
@@ -1749,6 +1959,7 @@ impl Value {
             (Value::Num(a), Value::Num(b)) => a.member(b, env)?.into(),
             (Value::Byte(a), Value::Byte(b)) => a.member(b, env)?.into(),
             (Value::Char(a), Value::Char(b)) => a.member(b, env)?.into(),
+            (Value::Complex(a), Value::Complex(b)) => a.member(b, env)?.into(),
             (Value::Func(a), Value::Func(b)) => a.member(b, env)?.into(),
             (Value::Num(a), Value::Byte(b)) => a.member(b, env)?.into(),
             (Value::Byte(a), Value::Num(b)) => a.member(b, env)?.into(),
@@ -1763,6 +1974,49 @@ impl Value {
     }
 }
 
+/// The number of row comparisons above which [`member`] and [`indexof`]
+/// hash the rows being searched instead of scanning them for each row
+const HASHED_LOOKUP_THRESHOLD: usize = 1024;
+
+fn use_hashed_lookup(searched_for: usize, searched_in: usize) -> bool {
+    searched_for > 1 && searched_for.saturating_mul(searched_in) > HASHED_LOOKUP_THRESHOLD
+}
+
+/// A row of an array that hashes and compares the same way as a row [`Array`]
+struct RowKey<'a, T>(&'a [T]);
+
+impl<'a, T: ArrayValue> Hash for RowKey<'a, T> {
+    fn hash<H: Hasher>(&self, hasher: &mut H) {
+        self.0.iter().for_each(|x| x.array_hash(hasher));
+    }
+}
+
+impl<'a, T: ArrayValue> PartialEq for RowKey<'a, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.len() == other.0.len() && self.0.iter().zip(other.0).all(|(a, b)| a.array_eq(b))
+    }
+}
+
+impl<'a, T: ArrayValue> Eq for RowKey<'a, T> {}
+
+impl<T: ArrayValue> Array<T> {
+    /// For each row, get the index of the first matching row of another array of the same rank
+    ///
+    /// The other array's rows are hashed, so each lookup takes constant time.
+    fn hashed_row_indices(&self, searched_in: &Self) -> Vec<Option<usize>> {
+        if self.shape[1..] != searched_in.shape[1..] {
+            return vec![None; self.row_count()];
+        }
+        let mut firsts = HashMap::with_capacity(searched_in.row_count());
+        for (i, row) in searched_in.row_slices().enumerate() {
+            firsts.entry(RowKey(row)).or_insert(i);
+        }
+        (self.row_slices())
+            .map(|row| firsts.get(&RowKey(row)).copied())
+            .collect()
+    }
+}
+
 impl<T: ArrayValue> Array<T> {
     pub fn member<U>(&self, of: &Array<U>, env: &Uiua) -> UiuaResult<Array<u8>>
     where
@@ -1772,6 +2026,16 @@ impl<T: ArrayValue> Array<T> {
         let elems = self;
         Ok(match elems.rank().cmp(&of.rank()) {
             Ordering::Equal => {
+                // Only arrays of the same type hash their elements the same way
+                if let Some(of) = (of as &dyn Any).downcast_ref::<Array<T>>() {
+                    if use_hashed_lookup(elems.row_count(), of.row_count()) {
+                        let result_data: Vec<u8> = (elems.hashed_row_indices(of).into_iter())
+                            .map(|i| i.is_some() as u8)
+                            .collect();
+                        let shape: Shape = self.shape.iter().cloned().take(1).collect();
+                        return Ok(Array::new(shape, result_data));
+                    }
+                }
                 let mut result_data = Vec::with_capacity(elems.row_count());
                 if elems.rank() == 1 {
                     for elem in &elems.data {
@@ -1826,6 +2090,7 @@ impl Value {
             (Value::Num(a), Value::Num(b)) => a.index_of(b, env)?.into(),
             (Value::Byte(a), Value::Byte(b)) => a.index_of(b, env)?.into(),
             (Value::Char(a), Value::Char(b)) => a.index_of(b, env)?.into(),
+            (Value::Complex(a), Value::Complex(b)) => a.index_of(b, env)?.into(),
             (Value::Func(a), Value::Func(b)) => a.index_of(b, env)?.into(),
             (Value::Num(a), Value::Byte(b)) => a.index_of(&b.clone().convert(), env)?.into(),
             (Value::Byte(a), Value::Num(b)) => a.clone().convert().index_of(b, env)?.into(),
@@ -1845,6 +2110,15 @@ impl<T: ArrayValue> Array<T> {
         let searched_for = self;
         Ok(match searched_for.rank().cmp(&searched_in.rank()) {
             Ordering::Equal => {
+                if use_hashed_lookup(searched_for.row_count(), searched_in.row_count()) {
+                    let not_found = searched_in.row_count();
+                    let result_data: Vec<f64> = (searched_for.hashed_row_indices(searched_in))
+                        .into_iter()
+                        .map(|i| i.unwrap_or(not_found) as f64)
+                        .collect();
+                    let shape: Shape = self.shape.iter().cloned().take(1).collect();
+                    return Ok(Array::new(shape, result_data));
+                }
                 let mut result_data = Vec::with_capacity(searched_for.row_count());
                 if searched_for.rank() == 1 {
                     for elem in &searched_for.data {
@@ -1910,3 +2184,95 @@ impl<T: ArrayValue> Array<T> {
         })
     }
 }
+
+impl Value {
+    /// Check which elements of two arrays are the same
+    ///
+    /// If the arrays have the same shape, the result is a mask that is `1` where the elements match.
+    /// Otherwise, the result is a list of the arrays' boxed shapes.
+    pub fn diff(&self, other: &Self) -> Self {
+        if self.shape() != other.shape() {
+            let shapes: Vec<Arc<Function>> = [self, other]
+                .into_iter()
+                .map(|val| {
+                    let shape: Value = val.shape().iter().copied().collect();
+                    Arc::new(Function::constant(shape))
+                })
+                .collect();
+            return Array::from(shapes).into();
+        }
+        fn mask<A, B>(a: &[A], b: &[B]) -> Vec<u8>
+        where
+            A: ArrayCmp<B>,
+        {
+            a.iter().zip(b).map(|(a, b)| a.array_eq(b) as u8).collect()
+        }
+        let mask = match (self, other) {
+            (Value::Num(a), Value::Num(b)) => mask(&a.data, &b.data),
+            (Value::Byte(a), Value::Byte(b)) => mask(&a.data, &b.data),
+            (Value::Char(a), Value::Char(b)) => mask(&a.data, &b.data),
+            (Value::Complex(a), Value::Complex(b)) => mask(&a.data, &b.data),
+            (Value::Func(a), Value::Func(b)) => mask(&a.data, &b.data),
+            (Value::Num(a), Value::Byte(b)) => mask(&a.data, &b.data),
+            (Value::Byte(a), Value::Num(b)) => mask(&a.data, &b.data),
+            _ => vec![0; self.flat_len()],
+        };
+        (Shape::from(self.shape()), mask).into()
+    }
+}
+
+/// Format an index the way it would be written in Uiua
+fn format_index(i: isize) -> String {
+    if i < 0 {
+        format!("¯{}", i.unsigned_abs())
+    } else {
+        i.to_string()
+    }
+}
+
+/// Convert a possibly negative index into an index into an axis with the given length
+///
+/// Negative indices count from the end of the axis.
+/// Returns `None` if the index is out of bounds.
+/// Both pick and select use these rules.
+fn normalize_index(i: isize, len: usize) -> Option<usize> {
+    let i = if i < 0 { i + len as isize } else { i };
+    (0..len as isize).contains(&i).then_some(i as usize)
+}
+
+/// Describe the valid indices of an axis with the given length
+fn valid_indices(len: usize) -> String {
+    if len == 0 {
+        "so there are no valid indices".into()
+    } else {
+        format!(
+            "so valid indices are {} to {}",
+            format_index(-(len as isize)),
+            len - 1
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn take_prefix_reuses_buffer() {
+        let env = Uiua::with_native_sys();
+        let array = Array::<f64>::from((0..100_000).map(f64::from).collect::<Vec<_>>());
+        let ptr = array.data.as_ptr();
+        // Shared data is viewed rather than copied
+        let shared = array.clone().take(&[1000], &env).unwrap();
+        assert_eq!(shared.data.as_ptr(), ptr);
+        assert_eq!(shared.shape.as_slice(), [1000]);
+        assert_eq!(shared.data[999], 999.0);
+        // Owned data is truncated in place
+        let owned = array.take(&[10], &env).unwrap();
+        assert_eq!(owned.data.as_ptr(), ptr);
+        assert_eq!(
+            owned.data.as_ref(),
+            (0..10).map(f64::from).collect::<Vec<_>>()
+        );
+    }
+}
@@ -1,6 +1,6 @@
 //! Algorithms for invert and under
 
-use std::{cell::RefCell, collections::HashMap, fmt};
+use std::{cell::RefCell, collections::HashMap, fmt, sync::Arc};
 
 use crate::{
     check::instrs_signature,
@@ -11,17 +11,33 @@ use crate::{
 
 impl Function {
     pub fn inverse(&self) -> Option<Self> {
-        Function::new_inferred(self.id.clone(), invert_instrs(&self.instrs)?).ok()
-    }
-    pub fn under(self) -> Option<(Self, Self)> {
+        let inverse = self.cache.inverse.get_or_init(|| {
+            #[cfg(test)]
+            tests::count_computation();
+            let instrs = invert_instrs(&self.instrs)?;
+            Function::new_inferred(self.id.clone(), instrs)
+                .ok()
+                .map(Arc::new)
+        });
+        inverse.as_deref().cloned()
+    }
+    pub fn under(&self) -> Option<(Self, Self)> {
         if let Some(f) = self.inverse() {
-            Some((self, f))
-        } else {
-            let (befores, afters) = under_instrs(&self.instrs)?;
-            let before = Function::new_inferred(self.id.clone(), befores).ok()?;
-            let after = Function::new_inferred(self.id, afters).ok()?;
-            Some((before, after))
+            return Some((self.clone(), f));
         }
+        let (before, after) = self
+            .cache
+            .under
+            .get_or_init(|| {
+                #[cfg(test)]
+                tests::count_computation();
+                let (befores, afters) = under_instrs(&self.instrs)?;
+                let before = Function::new_inferred(self.id.clone(), befores).ok()?;
+                let after = Function::new_inferred(self.id.clone(), afters).ok()?;
+                Some((before.into(), after.into()))
+            })
+            .as_ref()?;
+        Some((Function::clone(before), Function::clone(after)))
     }
 }
 
@@ -103,6 +119,7 @@ fn invert_instr_fragment(mut instrs: &[Instr]) -> Option<Vec<Instr>> {
         &(Val, IgnoreMany(Flip), ([Mul], [Div])),
         &(Val, ([Div], [Mul])),
         &invert_pow_pattern,
+        &invert_split_pattern,
         &invert_log_pattern,
         &invert_repeat_pattern,
     ];
@@ -165,9 +182,50 @@ fn under_instrs_impl(instrs: &[Instr]) -> Option<(Vec<Instr>, Vec<Instr>)> {
             let (before, after) = under_instrs(&instrs)?;
             return Some((before, after));
         }
+        [Push(f), Prim(prim @ (Dip | Gap), span)] => {
+            let f = f.as_function()?;
+            let mut instrs = f.instrs.clone();
+            if *prim == Dip {
+                let (count, span, kind) = (1, *span, TempKind::Inline);
+                instrs.insert(0, PushTemp { count, span, kind });
+                instrs.push(PopTemp { count, span, kind });
+            } else {
+                instrs.insert(0, Prim(Pop, *span));
+            }
+            return under_instrs(&instrs);
+        }
+        // Inlined gap
+        // The ignored value is popped before and is not restored after
+        [Prim(Pop, span), inner @ ..] => {
+            let (mut before, after) = under_instrs(inner)?;
+            before.insert(0, Prim(Pop, *span));
+            return Some((before, after));
+        }
         _ => {}
     }
 
+    // Inlined dip
+    // The dipped value is set aside both before and after
+    if let Some((inner, span)) = dip_block(instrs) {
+        let (mut before, mut after) = under_instrs(inner)?;
+        for instrs in [&mut before, &mut after] {
+            instrs.insert(
+                0,
+                PushTemp {
+                    count: 1,
+                    span,
+                    kind: TempKind::Inline,
+                },
+            );
+            instrs.push(PopTemp {
+                count: 1,
+                span,
+                kind: TempKind::Inline,
+            });
+        }
+        return Some((before, after));
+    }
+
     macro_rules! stash2 {
         ($before:expr, $after:expr) => {
             (
@@ -179,8 +237,10 @@ fn under_instrs_impl(instrs: &[Instr]) -> Option<(Vec<Instr>, Vec<Instr>)> {
     }
 
     let patterns: &[&dyn UnderPattern] = &[
+        &UnderPatternFn(under_group_pattern),
         &UnderPatternFn(under_from_inverse_pattern),
         &UnderPatternFn(under_temp_pattern),
+        &UnderPatternFn(under_fill_pattern),
         &(Val, stash2!(Take, Untake)),
         &stash2!(Take, Untake),
         &(Val, stash2!(Drop, Undrop)),
@@ -225,7 +285,12 @@ fn under_instrs_impl(instrs: &[Instr]) -> Option<(Vec<Instr>, Vec<Instr>)> {
         &(
             [Deshape],
             [Dup.i(), Shape.i(), PushTempN(1).i(), Deshape.i()],
-            [PopTempN(1).i(), Reshape.i()],
+            [PopTempN(1).i(), Undeshape.i()],
+        ),
+        &(
+            [Merge],
+            [Dup.i(), Shape.i(), PushTempN(1).i(), Merge.i()],
+            [PopTempN(1).i(), (-2).i(), Take.i(), Split.i()],
         ),
         &(
             [Pow],
@@ -318,6 +383,43 @@ fn under_instrs_impl(instrs: &[Instr]) -> Option<(Vec<Instr>, Vec<Instr>)> {
     None
 }
 
+/// Get the instructions inside an inlined dip that spans all of the given instructions
+fn dip_block(instrs: &[Instr]) -> Option<(&[Instr], usize)> {
+    let [Instr::PushTemp {
+        count: 1,
+        span,
+        kind: TempKind::Inline,
+    }, inner @ .., Instr::PopTemp {
+        count: 1,
+        span: end_span,
+        kind: TempKind::Inline,
+    }] = instrs
+    else {
+        return None;
+    };
+    if span != end_span {
+        return None;
+    }
+    // Make sure the first push is not popped before the end
+    let mut depth = 0;
+    for instr in inner {
+        match instr {
+            Instr::PushTemp {
+                count,
+                kind: TempKind::Inline,
+                ..
+            } => depth += count,
+            Instr::PopTemp {
+                count,
+                kind: TempKind::Inline,
+                ..
+            } => depth = usize::checked_sub(depth, *count)?,
+            _ => {}
+        }
+    }
+    Some((inner, *span))
+}
+
 trait AsInstr: fmt::Debug {
     fn as_instr(&self, span: usize) -> Instr;
     fn i(&self) -> Box<dyn AsInstr>
@@ -394,6 +496,70 @@ fn under_from_inverse_pattern(input: &[Instr]) -> Option<(&[Instr], Under)> {
     }
 }
 
+fn under_group_pattern(input: &[Instr]) -> Option<(&[Instr], Under)> {
+    let [f @ Instr::Push(f_val), Instr::Prim(prim, span), ..] = input else {
+        return None;
+    };
+    let unprim = match prim {
+        Primitive::Group => Primitive::Ungroup,
+        Primitive::Partition => Primitive::Unpartition,
+        _ => return None,
+    };
+    // Only functions that keep the groups intact can be inverted
+    if !matches!(
+        f_val.as_primitive(),
+        Some((Primitive::Box | Primitive::Identity, _))
+    ) {
+        return None;
+    }
+    let span = *span;
+    Some((
+        &input[2..],
+        (
+            vec![
+                Instr::Prim(Primitive::Over, span),
+                Instr::Prim(Primitive::Over, span),
+                PushTempN(2).as_instr(span),
+                f.clone(),
+                Instr::Prim(*prim, span),
+            ],
+            vec![
+                PopTempN(2).as_instr(span),
+                Instr::Prim(Primitive::Unroll, span),
+                Instr::Prim(unprim, span),
+            ],
+        ),
+    ))
+}
+
+/// The fill is only active for the before step
+fn under_fill_pattern(input: &[Instr]) -> Option<(&[Instr], Under)> {
+    let pushes = input
+        .iter()
+        .take_while(|instr| matches!(instr, Instr::Push(_)))
+        .count();
+    let (Some(Instr::Prim(Primitive::Fill, span)), Some(args_len)) =
+        (input.get(pushes), pushes.checked_sub(2))
+    else {
+        return None;
+    };
+    let (args, [Instr::Push(f), fill]) = input[..pushes].split_at(args_len) else {
+        return None;
+    };
+    let f = f.as_function()?;
+    // Constant arguments to the filled function are part of its under
+    let mut instrs = args.to_vec();
+    instrs.extend(f.instrs.iter().cloned());
+    let (before, after) = under_instrs(&instrs)?;
+    let before = Function::new_inferred(f.id.clone(), before).ok()?;
+    let before = vec![
+        Instr::push(before),
+        fill.clone(),
+        Instr::Prim(Primitive::Fill, *span),
+    ];
+    Some((&input[pushes + 1..], (before, after)))
+}
+
 fn under_temp_pattern(input: &[Instr]) -> Option<(&[Instr], Under)> {
     match input.split_first()? {
         (&Instr::PushTemp { count, span, kind }, input) => Some((
@@ -591,6 +757,15 @@ fn invert_pow_pattern(input: &[Instr]) -> Option<(&[Instr], Vec<Instr>)> {
     }
 }
 
+fn invert_split_pattern(input: &[Instr]) -> Option<(&[Instr], Vec<Instr>)> {
+    let (input, _) = Val.invert_extract(input)?;
+    if let (Instr::Prim(Primitive::Split, span), input) = input.split_first()? {
+        Some((input, vec![Instr::Prim(Primitive::Merge, *span)]))
+    } else {
+        None
+    }
+}
+
 fn invert_log_pattern(input: &[Instr]) -> Option<(&[Instr], Vec<Instr>)> {
     let (input, mut val) = Val.invert_extract(input)?;
     if let (Instr::Prim(Primitive::Log, span), input) = input.split_first()? {
@@ -672,3 +847,52 @@ impl UnderPattern for Val {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+    use crate::function::FunctionId;
+
+    thread_local! {
+        static COMPUTATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    pub(super) fn count_computation() {
+        COMPUTATIONS.with(|c| c.set(c.get() + 1));
+    }
+
+    fn computations() -> usize {
+        COMPUTATIONS.with(Cell::get)
+    }
+
+    #[test]
+    fn inverse_is_cached() {
+        let f = Function::new_inferred(
+            FunctionId::Main,
+            [Instr::push(2.0), Instr::Prim(Primitive::Add, 0)],
+        )
+        .unwrap();
+        let start = computations();
+        let a = f.inverse().unwrap();
+        let b = f.inverse().unwrap();
+        assert_eq!(a, b);
+        assert_eq!(computations() - start, 1);
+    }
+
+    #[test]
+    fn under_is_cached() {
+        let f = Function::new_inferred(
+            FunctionId::Main,
+            [Instr::push(2.0), Instr::Prim(Primitive::Take, 0)],
+        )
+        .unwrap();
+        let start = computations();
+        let a = f.under().unwrap();
+        let b = f.under().unwrap();
+        assert_eq!(a, b);
+        // One failed inversion and one under decomposition
+        assert_eq!(computations() - start, 2);
+    }
+}
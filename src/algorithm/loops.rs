@@ -1,16 +1,24 @@
 //! Algorithms for looping modifiers
 
-use std::ops::{Add, Div, Mul, Sub};
+use std::{
+    cell::Cell,
+    collections::BTreeMap,
+    mem::take,
+    ops::{Add, Div, Mul, Sub},
+    sync::Arc,
+};
 
+use rayon::prelude::*;
 use tinyvec::tiny_vec;
 
 use crate::{
-    algorithm::pervade::bin_pervade_generic,
+    algorithm::pervade::{self, bin_pervade_generic},
     array::{Array, ArrayValue, FormatShape, Shape},
     cowslice::cowslice,
+    function::Function,
     primitive::Primitive,
     run::{ArrayArg, FunctionArg},
-    value::Value,
+    value::{ArrayBuilder, Value},
     Uiua, UiuaResult,
 };
 
@@ -31,8 +39,8 @@ pub fn reduce(env: &mut Uiua) -> UiuaResult {
             Primitive::Mul => fast_reduce(nums, 1.0, Mul::mul),
             Primitive::Div if flipped => fast_reduce(nums, 1.0, Div::div),
             Primitive::Div => fast_reduce(nums, 1.0, flip(Div::div)),
-            Primitive::Max => fast_reduce(nums, f64::NEG_INFINITY, f64::max),
-            Primitive::Min => fast_reduce(nums, f64::INFINITY, f64::min),
+            Primitive::Max => fast_reduce(nums, f64::NEG_INFINITY, pervade::max::num_num),
+            Primitive::Min => fast_reduce(nums, f64::INFINITY, pervade::min::num_num),
             _ => return generic_fold1(f, Value::Num(nums), None, env),
         }),
         (Some((prim, flipped)), Value::Byte(bytes)) => env.push(match prim {
@@ -42,6 +50,13 @@ pub fn reduce(env: &mut Uiua) -> UiuaResult {
             Primitive::Mul => fast_reduce(bytes, 1.0, |a, b| a * f64::from(b)),
             Primitive::Div if flipped => fast_reduce(bytes, 1.0, |a, b| a / f64::from(b)),
             Primitive::Div => fast_reduce(bytes, 1.0, |a, b| f64::from(b) / a),
+            // Or and and of a list stop as soon as the result is known
+            Primitive::Max if bytes.rank() == 1 => {
+                short_circuit_reduce(&bytes.data, f64::NEG_INFINITY, u8::MAX, u8::max)
+            }
+            Primitive::Min if bytes.rank() == 1 => {
+                short_circuit_reduce(&bytes.data, f64::INFINITY, 0, u8::min)
+            }
             Primitive::Max => fast_reduce(bytes, f64::NEG_INFINITY, |a, b| a.max(f64::from(b))),
             Primitive::Min => fast_reduce(bytes, f64::INFINITY, |a, b| a.min(f64::from(b))),
             _ => return generic_fold1(f, Value::Byte(bytes), None, env),
@@ -51,6 +66,46 @@ pub fn reduce(env: &mut Uiua) -> UiuaResult {
     Ok(())
 }
 
+pub fn reduce_axis(env: &mut Uiua) -> UiuaResult {
+    crate::profile_function!();
+    let f = env.pop(FunctionArg(1))?;
+    let axis = env
+        .pop(ArrayArg(1))?
+        .as_int(env, "Axis must be an integer")?;
+    let mut xs = env.pop(ArrayArg(2))?;
+    let rank = xs.rank() as isize;
+    let normalized = if axis < 0 { axis + rank } else { axis };
+    if !(0..rank).contains(&normalized) {
+        return Err(env.error(format!(
+            "Cannot reduce along axis {axis} of a rank {rank} array"
+        )));
+    }
+    xs.move_axis_to_front(normalized as usize);
+    env.push(xs);
+    env.push(f);
+    reduce(env)
+}
+
+/// Reduce a list of bytes, stopping once the accumulator reaches `absorbing`
+fn short_circuit_reduce(
+    data: &[u8],
+    identity: f64,
+    absorbing: u8,
+    f: fn(u8, u8) -> u8,
+) -> Array<f64> {
+    let mut vals = data.iter().copied();
+    let Some(mut acc) = vals.next() else {
+        return identity.into();
+    };
+    for x in vals {
+        if acc == absorbing {
+            break;
+        }
+        acc = f(acc, x);
+    }
+    f64::from(acc).into()
+}
+
 pub fn fast_reduce<T: ArrayValue + Into<R>, R: ArrayValue>(
     mut arr: Array<T>,
     identity: R,
@@ -194,6 +249,116 @@ fn generic_fold_n(f: Value, env: &mut Uiua) -> UiuaResult {
     Ok(())
 }
 
+pub fn map_accum(env: &mut Uiua) -> UiuaResult {
+    crate::profile_function!();
+    let f = env.pop(FunctionArg(1))?;
+    let sig = f.signature();
+    if sig != (2, 2) {
+        return Err(env.error(format!(
+            "Mapaccum's function's signature must be |2.2, but it is {sig}"
+        )));
+    }
+    let mut acc = env.pop(ArrayArg(1))?;
+    let xs = env.pop(ArrayArg(2))?;
+    let mut outputs = ArrayBuilder::new();
+    for row in xs.into_rows() {
+        env.check_interrupt()?;
+        env.push(row);
+        env.push(acc);
+        env.call_error_on_break(f.clone(), "break is not allowed in mapaccum")?;
+        acc = env.pop("accumulated function result")?;
+        outputs.push_row(env.pop("mapped function result")?, env)?;
+    }
+    env.push(outputs.finish());
+    env.push(acc);
+    Ok(())
+}
+
+pub fn window_reduce(env: &mut Uiua) -> UiuaResult {
+    crate::profile_function!();
+    let f = env.pop(FunctionArg(1))?;
+    let size = env
+        .pop(ArrayArg(1))?
+        .as_nat(env, "Window size must be a natural number")?;
+    let xs = env.pop(ArrayArg(2))?;
+    let sig = f.signature();
+    if sig.args != 2 || sig.outputs != 1 {
+        return Err(env.error(format!(
+            "windowreduce's function must take 2 arguments and return 1 value, \
+            but its signature is {sig}"
+        )));
+    }
+    if size == 0 {
+        return Err(env.error("Cannot reduce windows of size 0"));
+    }
+    if xs.rank() == 0 {
+        return Err(env.error("Cannot reduce windows of a scalar"));
+    }
+    let fast = f.as_flipped_primitive().and_then(|(prim, flipped)| {
+        Some(match prim {
+            Primitive::Add => Add::add as fn(f64, f64) -> f64,
+            Primitive::Sub if flipped => Sub::sub,
+            Primitive::Sub => |a, b| b - a,
+            Primitive::Mul => Mul::mul,
+            Primitive::Div if flipped => Div::div,
+            Primitive::Div => |a, b| b / a,
+            Primitive::Max => pervade::max::num_num,
+            Primitive::Min => pervade::min::num_num,
+            _ => return None,
+        })
+    });
+    match (fast, xs) {
+        (Some(f), Value::Num(nums)) => env.push(fast_window_reduce(&nums, size, f)),
+        (Some(f), Value::Byte(bytes)) => env.push(fast_window_reduce(&bytes.convert(), size, f)),
+        (_, xs) => generic_window_reduce(f, xs, size, env)?,
+    }
+    Ok(())
+}
+
+/// Reduce each window of `size` rows with a known numeric function
+fn fast_window_reduce(arr: &Array<f64>, size: usize, f: fn(f64, f64) -> f64) -> Array<f64> {
+    let row_len = arr.row_len();
+    let window_count = (arr.row_count() + 1).saturating_sub(size);
+    let mut data = Vec::with_capacity(window_count * row_len);
+    for w in 0..window_count {
+        let window = &arr.data[w * row_len..(w + size) * row_len];
+        for j in 0..row_len {
+            let mut acc = window[j];
+            for k in 1..size {
+                acc = f(acc, window[k * row_len + j]);
+            }
+            data.push(acc);
+        }
+    }
+    let mut shape = arr.shape.clone();
+    shape[0] = window_count;
+    Array::new(shape, data)
+}
+
+/// Reduce each window of `size` rows by calling a function
+fn generic_window_reduce(f: Value, xs: Value, size: usize, env: &mut Uiua) -> UiuaResult {
+    let rows: Vec<Value> = xs.rows().collect();
+    let window_count = (rows.len() + 1).saturating_sub(size);
+    if window_count == 0 {
+        env.push(Value::from(0.0).take(xs, env)?);
+        return Ok(());
+    }
+    let mut reduced = Vec::with_capacity(window_count);
+    for window in rows.windows(size) {
+        let mut acc = window[0].clone();
+        for row in &window[1..] {
+            env.push(row.clone());
+            env.push(acc);
+            env.call_error_on_break(f.clone(), "break is not allowed in windowreduce")?;
+            acc = env.pop("windowreduce's function result")?;
+        }
+        reduced.push(acc);
+    }
+    let reduced = Value::from_row_values(reduced, env)?;
+    env.push(reduced);
+    Ok(())
+}
+
 pub fn scan(env: &mut Uiua) -> UiuaResult {
     crate::profile_function!();
     let f = env.pop(FunctionArg(1))?;
@@ -210,8 +375,8 @@ pub fn scan(env: &mut Uiua) -> UiuaResult {
                 Primitive::Mul => fast_scan(nums, Mul::mul),
                 Primitive::Div if flipped => fast_scan(nums, Div::div),
                 Primitive::Div => fast_scan(nums, flip(Div::div)),
-                Primitive::Max => fast_scan(nums, f64::max),
-                Primitive::Min => fast_scan(nums, f64::min),
+                Primitive::Max => fast_scan(nums, pervade::max::num_num),
+                Primitive::Min => fast_scan(nums, pervade::min::num_num),
                 _ => return generic_scan(f, Value::Num(nums), env),
             };
             env.push(arr);
@@ -298,13 +463,74 @@ fn generic_scan(f: Value, xs: Value, env: &mut Uiua) -> UiuaResult {
             break;
         }
     }
-    env.push(Value::from_row_values(
-        scanned.into_iter().chain(rows),
-        env,
-    )?);
+    scanned.extend(rows);
+    if let Some(boxed) = box_ragged(&mut scanned, env) {
+        env.push(Array::from(boxed));
+        return Ok(());
+    }
+    env.push(Value::from_row_values(scanned, env)?);
     Ok(())
 }
 
+/// Box the results of a loop if they have different shapes
+/// and there is no fill value of their type to make them uniform
+///
+/// Returns `None` and leaves the results alone if they can be combined.
+fn box_ragged(results: &mut Vec<Value>, env: &Uiua) -> Option<Vec<Arc<Function>>> {
+    let uniform = results.windows(2).all(|w| w[0].shape() == w[1].shape());
+    let has_fill = |val: &Value| match val {
+        Value::Num(_) => env.num_fill().is_some(),
+        Value::Byte(_) => env.byte_fill().is_some(),
+        Value::Complex(_) => env.complex_fill().is_some(),
+        Value::Char(_) => env.char_fill().is_some(),
+        Value::Func(_) => env.func_fill().is_some(),
+    };
+    if uniform || results.iter().all(has_fill) {
+        return None;
+    }
+    let boxed = take(results)
+        .into_iter()
+        .map(|item| Arc::new(Function::constant(item)))
+        .collect();
+    Some(boxed)
+}
+
+/// How many iterations of a loop happen between progress reports
+const PROGRESS_INTERVAL: usize = 1000;
+
+/// Reports the progress of a loop to the backend
+///
+/// Whether the backend wants progress reports is only checked once per loop,
+/// so this is cheap when it does not.
+struct Progress {
+    done: Cell<usize>,
+    next_report: Cell<usize>,
+    total: usize,
+    enabled: bool,
+}
+
+impl Progress {
+    fn new(total: usize, env: &Uiua) -> Self {
+        Progress {
+            done: Cell::new(0),
+            next_report: Cell::new(PROGRESS_INTERVAL),
+            total,
+            enabled: env.backend.reports_progress(),
+        }
+    }
+    fn step(&self, env: &Uiua) {
+        if !self.enabled {
+            return;
+        }
+        let done = self.done.get() + 1;
+        self.done.set(done);
+        if done == self.next_report.get() || done == self.total {
+            self.next_report.set(done + PROGRESS_INTERVAL);
+            env.backend.report_progress(done, self.total);
+        }
+    }
+}
+
 pub fn each(env: &mut Uiua) -> UiuaResult {
     crate::profile_function!();
     let f = env.pop(FunctionArg(1))?;
@@ -323,6 +549,10 @@ pub fn each(env: &mut Uiua) -> UiuaResult {
         0 => Ok(()),
         1 => {
             let xs = env.pop(ArrayArg(1))?;
+            if output && each_is_pervasive(&f, &[&xs]) {
+                env.push(xs);
+                return env.call(f);
+            }
             if output {
                 each1_1(f, xs, env)
             } else {
@@ -332,6 +562,11 @@ pub fn each(env: &mut Uiua) -> UiuaResult {
         2 => {
             let xs = env.pop(ArrayArg(1))?;
             let ys = env.pop(ArrayArg(2))?;
+            if output && each_is_pervasive(&f, &[&xs, &ys]) {
+                env.push(ys);
+                env.push(xs);
+                return env.call(f);
+            }
             if output {
                 each2_1(f, xs, ys, env)
             } else {
@@ -352,14 +587,50 @@ pub fn each(env: &mut Uiua) -> UiuaResult {
     }
 }
 
+/// Check if each's function is a single pervasive primitive that can
+/// be applied to the whole arrays at once with the same result
+fn each_is_pervasive(f: &Value, args: &[&Value]) -> bool {
+    let Some((prim, false)) = f.as_flipped_primitive() else {
+        return false;
+    };
+    prim.class().is_pervasive()
+        && prim.args() == Some(args.len() as u8)
+        && args
+            .iter()
+            .all(|arg| !matches!(arg, Value::Func(_)) && arg.flat_len() > 0)
+        && args.windows(2).all(|w| w[0].shape() == w[1].shape())
+}
+
+/// The minimum number of elements for [`each`] to run its function in parallel
+const PARALLEL_EACH_THRESHOLD: usize = 10_000;
+
+/// Check if each's function can be run on separate threads
+/// without changing the observable behavior
+fn each_can_run_parallel(f: &Value, xs: &Value, env: &Uiua) -> bool {
+    env.parallel_each()
+        && xs.flat_len() >= PARALLEL_EACH_THRESHOLD
+        && !matches!(xs, Value::Func(_))
+        && !env.has_step_budget()
+        && !env.backend.reports_progress()
+        && f.as_func_array()
+            .into_iter()
+            .flat_map(|fs| &fs.data)
+            .all(|f| f.is_pure())
+}
+
 fn each1_1(f: Value, xs: Value, env: &mut Uiua) -> UiuaResult {
+    if each_can_run_parallel(&f, &xs, env) {
+        return each1_1_parallel(f, xs, env);
+    }
     let mut new_values = Vec::with_capacity(xs.flat_len());
     let mut new_shape = Shape::from(xs.shape());
+    let progress = Progress::new(xs.flat_len(), env);
     let mut old_values = xs.into_flat_values();
     for val in old_values.by_ref() {
         env.push(val);
         let broke = env.call_catch_break(f.clone())?;
         new_values.push(env.pop("each's function result")?);
+        progress.step(env);
         if broke {
             for row in old_values {
                 new_values.push(row);
@@ -374,13 +645,54 @@ fn each1_1(f: Value, xs: Value, env: &mut Uiua) -> UiuaResult {
     Ok(())
 }
 
+/// Run each's function on chunks of the elements in parallel,
+/// each chunk with its own copy of the environment
+fn each1_1_parallel(f: Value, xs: Value, env: &mut Uiua) -> UiuaResult {
+    let mut new_shape = Shape::from(xs.shape());
+    let len = xs.flat_len();
+    let chunk_len = len.div_ceil(rayon::current_num_threads()).max(1);
+    let mut old_values = xs.into_flat_values();
+    let mut chunks = Vec::new();
+    loop {
+        let chunk: Vec<Value> = old_values.by_ref().take(chunk_len).collect();
+        if chunk.is_empty() {
+            break;
+        }
+        chunks.push((env.clone(), chunk));
+    }
+    let results: Vec<UiuaResult<Vec<Value>>> = chunks
+        .into_par_iter()
+        .map(|(mut env, chunk)| {
+            let mut new_values = Vec::with_capacity(chunk.len());
+            for val in chunk {
+                env.push(val);
+                env.call(f.clone())?;
+                new_values.push(env.pop("each's function result")?);
+            }
+            Ok(new_values)
+        })
+        .collect();
+    // Report the error from the earliest element, like the sequential path would
+    let mut new_values = Vec::with_capacity(len);
+    for result in results {
+        new_values.extend(result?);
+    }
+    let mut eached = Value::from_row_values(new_values, env)?;
+    new_shape.extend_from_slice(&eached.shape()[1..]);
+    *eached.shape_mut() = new_shape;
+    env.push(eached);
+    Ok(())
+}
+
 fn each1_0(f: Value, xs: Value, env: &mut Uiua) -> UiuaResult {
+    let progress = Progress::new(xs.flat_len(), env);
     let values = xs.into_flat_values();
     for val in values {
         env.push(val);
         if env.call_catch_break(f.clone())? {
             break;
         }
+        progress.step(env);
     }
     Ok(())
 }
@@ -390,6 +702,8 @@ fn each2_1(f: Value, xs: Value, ys: Value, env: &mut Uiua) -> UiuaResult {
     let ys_shape = ys.shape().to_vec();
     let xs_values: Vec<_> = xs.into_flat_values().collect();
     let ys_values: Vec<_> = ys.into_flat_values().collect();
+    let progress = Progress::new(xs_values.len().max(ys_values.len()), env);
+    let progress = &progress;
     let (mut shape, values) = bin_pervade_generic(
         &xs_shape,
         xs_values,
@@ -400,6 +714,7 @@ fn each2_1(f: Value, xs: Value, ys: Value, env: &mut Uiua) -> UiuaResult {
             env.push(y);
             env.push(x);
             env.call_error_on_break(f.clone(), "break is not allowed in multi-argument each")?;
+            progress.step(env);
             env.pop("each's function result")
         },
     )?;
@@ -415,6 +730,8 @@ fn each2_0(f: Value, xs: Value, ys: Value, env: &mut Uiua) -> UiuaResult {
     let ys_shape = ys.shape().to_vec();
     let xs_values: Vec<_> = xs.into_flat_values().collect();
     let ys_values: Vec<_> = ys.into_flat_values().collect();
+    let progress = Progress::new(xs_values.len().max(ys_values.len()), env);
+    let progress = &progress;
     bin_pervade_generic(
         &xs_shape,
         xs_values,
@@ -425,6 +742,7 @@ fn each2_0(f: Value, xs: Value, ys: Value, env: &mut Uiua) -> UiuaResult {
             env.push(y);
             env.push(x);
             env.call_error_on_break(f.clone(), "break is not allowed multi-argument in each")?;
+            progress.step(env);
             Ok(())
         },
     )?;
@@ -443,6 +761,7 @@ fn eachn_1(f: Value, args: Vec<Value>, env: &mut Uiua) -> UiuaResult {
         }
     }
     let elem_count = args[0].flat_len();
+    let progress = Progress::new(elem_count, env);
     let mut arg_elems: Vec<_> = args.into_iter().map(|v| v.into_flat_values()).collect();
     let mut new_values = Vec::new();
     for _ in 0..elem_count {
@@ -451,6 +770,7 @@ fn eachn_1(f: Value, args: Vec<Value>, env: &mut Uiua) -> UiuaResult {
         }
         env.call_error_on_break(f.clone(), "break is not allowed in multi-argument each")?;
         new_values.push(env.pop("each's function result")?);
+        progress.step(env);
     }
     let eached = Value::from_row_values(new_values, env)?;
     env.push(eached);
@@ -469,12 +789,14 @@ fn eachn_0(f: Value, args: Vec<Value>, env: &mut Uiua) -> UiuaResult {
         }
     }
     let elem_count = args[0].flat_len();
+    let progress = Progress::new(elem_count, env);
     let mut arg_elems: Vec<_> = args.into_iter().map(|v| v.into_flat_values()).collect();
     for _ in 0..elem_count {
         for arg in arg_elems.iter_mut().rev() {
             env.push(arg.next().unwrap());
         }
         env.call_error_on_break(f.clone(), "break is not allowed in multi-argument each")?;
+        progress.step(env);
     }
     Ok(())
 }
@@ -497,6 +819,10 @@ pub fn rows(env: &mut Uiua) -> UiuaResult {
         0 => Ok(()),
         1 => {
             let xs = env.pop(ArrayArg(1))?;
+            if output && each_is_pervasive(&f, &[&xs]) {
+                env.push(xs);
+                return env.call(f);
+            }
             if output {
                 rows1_1(f, xs, env)
             } else {
@@ -528,11 +854,13 @@ pub fn rows(env: &mut Uiua) -> UiuaResult {
 
 fn rows1_1(f: Value, xs: Value, env: &mut Uiua) -> UiuaResult {
     let mut new_rows = Value::builder(xs.row_count());
+    let progress = Progress::new(xs.row_count(), env);
     let mut old_rows = xs.into_rows();
     for row in old_rows.by_ref() {
         env.push(row);
         let broke = env.call_catch_break(f.clone())?;
         new_rows.add_row(env.pop("rows' function result")?, &env)?;
+        progress.step(env);
         if broke {
             for row in old_rows {
                 new_rows.add_row(row, &env)?;
@@ -545,12 +873,14 @@ fn rows1_1(f: Value, xs: Value, env: &mut Uiua) -> UiuaResult {
 }
 
 fn rows1_0(f: Value, xs: Value, env: &mut Uiua) -> UiuaResult {
+    let progress = Progress::new(xs.row_count(), env);
     for row in xs.into_rows() {
         env.push(row);
         let broke = env.call_catch_break(f.clone())?;
         if broke {
             break;
         }
+        progress.step(env);
     }
     Ok(())
 }
@@ -564,6 +894,7 @@ fn rows2_1(f: Value, xs: Value, ys: Value, env: &mut Uiua) -> UiuaResult {
         )));
     }
     let mut new_rows = Vec::with_capacity(xs.row_count());
+    let progress = Progress::new(xs.row_count(), env);
     let x_rows = xs.into_rows();
     let y_rows = ys.into_rows();
     for (x, y) in x_rows.into_iter().zip(y_rows) {
@@ -571,6 +902,7 @@ fn rows2_1(f: Value, xs: Value, ys: Value, env: &mut Uiua) -> UiuaResult {
         env.push(x);
         env.call_error_on_break(f.clone(), "break is not allowed in multi-argument rows")?;
         new_rows.push(env.pop("rows's function result")?);
+        progress.step(env);
     }
     env.push(Value::from_row_values(new_rows, env)?);
     Ok(())
@@ -584,12 +916,14 @@ fn rows2_0(f: Value, xs: Value, ys: Value, env: &mut Uiua) -> UiuaResult {
             ys.row_count()
         )));
     }
+    let progress = Progress::new(xs.row_count(), env);
     let x_rows = xs.into_rows();
     let y_rows = ys.into_rows();
     for (x, y) in x_rows.into_iter().zip(y_rows) {
         env.push(y);
         env.push(x);
         env.call_error_on_break(f.clone(), "break is not allowed in multi-argument rows")?;
+        progress.step(env);
     }
     Ok(())
 }
@@ -606,6 +940,7 @@ fn rowsn_1(f: Value, args: Vec<Value>, env: &mut Uiua) -> UiuaResult {
         }
     }
     let row_count = args[0].row_count();
+    let progress = Progress::new(row_count, env);
     let mut arg_elems: Vec<_> = args.into_iter().map(|v| v.into_rows()).collect();
     let mut new_values = Vec::new();
     for _ in 0..row_count {
@@ -614,6 +949,7 @@ fn rowsn_1(f: Value, args: Vec<Value>, env: &mut Uiua) -> UiuaResult {
         }
         env.call_error_on_break(f.clone(), "break is not allowed in multi-argument each")?;
         new_values.push(env.pop("each's function result")?);
+        progress.step(env);
     }
     let eached = Value::from_row_values(new_values, env)?;
     env.push(eached);
@@ -622,12 +958,14 @@ fn rowsn_1(f: Value, args: Vec<Value>, env: &mut Uiua) -> UiuaResult {
 
 fn rowsn_0(f: Value, args: Vec<Value>, env: &mut Uiua) -> UiuaResult {
     let row_count = args[0].row_count();
+    let progress = Progress::new(row_count, env);
     let mut arg_elems: Vec<_> = args.into_iter().map(|v| v.into_rows()).collect();
     for _ in 0..row_count {
         for arg in arg_elems.iter_mut().rev() {
             env.push(arg.next().unwrap());
         }
         env.call_error_on_break(f.clone(), "break is not allowed in multi-argument each")?;
+        progress.step(env);
     }
     Ok(())
 }
@@ -799,8 +1137,8 @@ fn table_nums(
         Primitive::Mul => env.push(fast_table(xs, ys, Mul::mul)),
         Primitive::Div if flipped => env.push(fast_table(xs, ys, Div::div)),
         Primitive::Div => env.push(fast_table(xs, ys, flip(Div::div))),
-        Primitive::Min => env.push(fast_table(xs, ys, f64::min)),
-        Primitive::Max => env.push(fast_table(xs, ys, f64::max)),
+        Primitive::Min => env.push(fast_table(xs, ys, pervade::min::num_num)),
+        Primitive::Max => env.push(fast_table(xs, ys, pervade::max::num_num)),
         Primitive::Join | Primitive::Couple => env.push(fast_table_join_or_couple(xs, ys)),
         _ => return Err((xs, ys)),
     }
@@ -847,7 +1185,7 @@ fn generic_table(f: Value, xs: Value, ys: Value, env: &mut Uiua) -> UiuaResult {
     }
     let mut new_shape = Shape::from(xs.shape());
     new_shape.extend_from_slice(ys.shape());
-    let mut items = Value::builder(xs.flat_len() * ys.flat_len());
+    let mut results = Vec::with_capacity(xs.flat_len() * ys.flat_len());
     let y_values = ys.into_flat_values().collect::<Vec<_>>();
     for x in xs.into_flat_values() {
         for y in y_values.iter().cloned() {
@@ -856,9 +1194,17 @@ fn generic_table(f: Value, xs: Value, ys: Value, env: &mut Uiua) -> UiuaResult {
             env.call_error_on_break(f.clone(), "break is not allowed in table")?;
             let item = env.pop("tabled function result")?;
             item.validate_shape();
-            items.add_row(item, &env)?;
+            results.push(item);
         }
     }
+    if let Some(boxed) = box_ragged(&mut results, env) {
+        env.push(Array::new(new_shape, boxed));
+        return Ok(());
+    }
+    let mut items = Value::builder(results.len());
+    for item in results {
+        items.add_row(item, &env)?;
+    }
     let mut tabled = items.finish();
     new_shape.extend_from_slice(&tabled.shape()[1..]);
     *tabled.shape_mut() = new_shape;
@@ -910,6 +1256,7 @@ pub fn repeat(env: &mut Uiua) -> UiuaResult {
     if n.is_infinite() {
         let f = if n < 0.0 { f.invert(env)? } else { f };
         loop {
+            env.check_interrupt()?;
             if env.call_catch_break(f.clone())? {
                 break;
             }
@@ -920,6 +1267,7 @@ pub fn repeat(env: &mut Uiua) -> UiuaResult {
         };
         let f = if n < 0.0 { f.invert(env)? } else { f };
         for _ in 0..n.abs() as usize {
+            env.check_interrupt()?;
             if env.call_catch_break(f.clone())? {
                 return Ok(());
             }
@@ -928,6 +1276,93 @@ pub fn repeat(env: &mut Uiua) -> UiuaResult {
     Ok(())
 }
 
+pub fn iterate(env: &mut Uiua) -> UiuaResult {
+    crate::profile_function!();
+    let f = env.pop(FunctionArg(1))?;
+    let sig = f.signature();
+    if sig != (1, 1) {
+        return Err(env.error(format!(
+            "Iterate's function's signature must be |1.1, but it is {sig}"
+        )));
+    }
+    let n = env
+        .pop(2)?
+        .as_nat(env, "Iterations must be a single natural number")?;
+    let mut acc = env.pop(ArrayArg(1))?;
+    let mut states = Vec::with_capacity(n.min(1024) + 1);
+    states.push(acc.clone());
+    for _ in 0..n {
+        env.check_interrupt()?;
+        env.push(acc);
+        let broke = env.call_catch_break(f.clone())?;
+        acc = env.pop("iterated function result")?;
+        states.push(acc.clone());
+        if broke {
+            break;
+        }
+    }
+    env.push(Value::from_row_values(states, env)?);
+    Ok(())
+}
+
+pub fn collect(env: &mut Uiua) -> UiuaResult {
+    crate::profile_function!();
+    let f = env.pop(FunctionArg(1))?;
+    let sig = f.signature();
+    if sig.outputs != sig.args + 1 {
+        return Err(env.error(format!(
+            "Collect's function must return 1 more value than it takes, \
+            but its signature is {sig}"
+        )));
+    }
+    let n = env
+        .pop(2)?
+        .as_nat(env, "Collect count must be a single natural number")?;
+    let mut rows = ArrayBuilder::new();
+    for _ in 0..n {
+        env.check_interrupt()?;
+        env.call_error_on_break(f.clone(), "break is not allowed in collect")?;
+        rows.push_row(env.pop("collected row")?, env)?;
+    }
+    env.push(rows.finish());
+    Ok(())
+}
+
+pub fn do_while(env: &mut Uiua) -> UiuaResult {
+    crate::profile_function!();
+    let body = env.pop(FunctionArg(1))?;
+    let cond = env.pop(FunctionArg(2))?;
+    let (body_sig, cond_sig) = (body.signature(), cond.signature());
+    if cond_sig.outputs != 1 {
+        return Err(env.error(format!(
+            "Do's condition function must return 1 value, but its signature is {cond_sig}"
+        )));
+    }
+    if body_sig.args != body_sig.outputs {
+        return Err(env.error(format!(
+            "Do's body function must return as many values as it takes, \
+            but its signature is {body_sig}"
+        )));
+    }
+    loop {
+        env.step()?;
+        // The condition is called on copies of its arguments
+        for val in env.clone_stack_top(cond_sig.args) {
+            env.push(val);
+        }
+        env.call_error_on_break(cond.clone(), "break is not allowed in do's condition")?;
+        let keep_going =
+            (env.pop("condition result")?).as_bool(env, "Do's condition must return a boolean")?;
+        if !keep_going {
+            break;
+        }
+        if env.call_catch_break(body.clone())? {
+            break;
+        }
+    }
+    Ok(())
+}
+
 pub fn level(env: &mut Uiua) -> UiuaResult {
     crate::profile_function!();
     let get_ns = env.pop(FunctionArg(1))?;
@@ -1194,6 +1629,10 @@ impl Value {
                 .partition_groups(markers, env)?
                 .map(Into::into)
                 .collect(),
+            Value::Complex(arr) => arr
+                .partition_groups(markers, env)?
+                .map(Into::into)
+                .collect(),
             Value::Char(arr) => arr
                 .partition_groups(markers, env)?
                 .map(Into::into)
@@ -1244,11 +1683,174 @@ pub fn group(env: &mut Uiua) -> UiuaResult {
     )
 }
 
+pub fn group_reduce(env: &mut Uiua) -> UiuaResult {
+    crate::profile_function!();
+    let f = env.pop(FunctionArg(1))?;
+    let sig = f.signature();
+    if sig.args != 2 || sig.outputs != 1 {
+        return Err(env.error(format!(
+            "groupreduce's function must take 2 arguments and return 1 value, \
+            but its signature is {sig}"
+        )));
+    }
+    let indices = env.pop(ArrayArg(1))?;
+    let indices = indices.as_indices(env, "Group indices must be a list of integers")?;
+    let xs = env.pop(ArrayArg(2))?;
+    if indices.len() != xs.row_count() {
+        return Err(env.error(format!(
+            "Cannot group array of shape {} with indices of length {}",
+            xs.format_shape(),
+            indices.len()
+        )));
+    }
+    let group_count = indices
+        .iter()
+        .max()
+        .map_or(0, |&max| (max.max(-1) + 1) as usize);
+    // Commutative numeric reductions are done without splitting the array into rows
+    if let Some((prim, _)) = f.as_flipped_primitive() {
+        let op = match prim {
+            Primitive::Add => Some((0.0, Add::add as NumReduceFn)),
+            Primitive::Mul => Some((1.0, Mul::mul as NumReduceFn)),
+            Primitive::Max => Some((f64::NEG_INFINITY, pervade::max::num_num as NumReduceFn)),
+            Primitive::Min => Some((f64::INFINITY, pervade::min::num_num as NumReduceFn)),
+            _ => None,
+        };
+        if let Some((identity, op)) = op {
+            match &xs {
+                Value::Num(arr) => {
+                    let res = fast_group_reduce(arr, &indices, group_count, identity, op);
+                    env.push(res);
+                    return Ok(());
+                }
+                Value::Byte(arr) => {
+                    let res = fast_group_reduce(arr, &indices, group_count, identity, op);
+                    env.push(res);
+                    return Ok(());
+                }
+                _ => {}
+            }
+        }
+    }
+    let mut accs: Vec<Option<Value>> = vec![None; group_count];
+    for (row, &g) in xs.into_rows().zip(&indices) {
+        if g < 0 {
+            continue;
+        }
+        let g = g as usize;
+        accs[g] = Some(match accs[g].take() {
+            None => row,
+            Some(acc) => {
+                env.push(row);
+                env.push(acc);
+                env.call_error_on_break(f.clone(), "break is not allowed in groupreduce")?;
+                env.pop("groupreduce's function result")?
+            }
+        });
+    }
+    let mut rows = Vec::with_capacity(group_count);
+    for (g, acc) in accs.into_iter().enumerate() {
+        rows.push(acc.ok_or_else(|| env.error(format!("Cannot reduce empty group {g}")))?);
+    }
+    let res = Value::from_row_values(rows, env)?;
+    env.push(res);
+    Ok(())
+}
+
+type NumReduceFn = fn(f64, f64) -> f64;
+
+fn fast_group_reduce<T: ArrayValue + Into<f64>>(
+    arr: &Array<T>,
+    indices: &[isize],
+    group_count: usize,
+    identity: f64,
+    f: NumReduceFn,
+) -> Array<f64> {
+    let row_len = arr.row_len();
+    let mut data = vec![identity; group_count * row_len];
+    for (r, &g) in indices.iter().enumerate() {
+        if g < 0 {
+            continue;
+        }
+        let acc = &mut data[g as usize * row_len..][..row_len];
+        for (a, x) in acc.iter_mut().zip(arr.row_slice(r)) {
+            *a = f(*a, x.clone().into());
+        }
+    }
+    let mut shape = arr.shape.clone();
+    if shape.is_empty() {
+        shape.push(group_count);
+    } else {
+        shape[0] = group_count;
+    }
+    Array::new(shape, data)
+}
+
+pub fn classify_by(env: &mut Uiua) -> UiuaResult {
+    crate::profile_function!();
+    let key = env.pop(FunctionArg(1))?;
+    let xs = env.pop(ArrayArg(1))?;
+    let classes = classify_keys("classifyby", key, &xs, env)?;
+    env.push(Value::from_iter(classes));
+    Ok(())
+}
+
+pub fn group_by(env: &mut Uiua) -> UiuaResult {
+    crate::profile_function!();
+    let key = env.pop(FunctionArg(1))?;
+    let f = env.pop(FunctionArg(2))?;
+    let sig = f.signature();
+    if sig.args != 1 || sig.outputs != 1 {
+        return Err(env.error(format!(
+            "groupby's function must take 1 argument and return 1 value, \
+            but its signature is {sig}"
+        )));
+    }
+    let xs = env.pop(ArrayArg(1))?;
+    let classes = classify_keys("groupby", key, &xs, env)?;
+    let classes: Vec<isize> = classes.into_iter().map(|c| c as isize).collect();
+    let groups = xs.group_groups(&classes, env)?;
+    let mut rows = Vec::with_capacity(groups.len());
+    for group in groups {
+        env.push(group);
+        env.call_error_on_break(f.clone(), "break is not allowed in groupby")?;
+        rows.push(env.pop("groupby's function result")?);
+    }
+    let res = Value::from_row_values(rows, env)?;
+    env.push(res);
+    Ok(())
+}
+
+/// Classify the rows of an array by the keys a function produces for them
+///
+/// Keys are compared as whole values, so a key function that returns
+/// a list groups rows by all of the list's elements jointly.
+fn classify_keys(name: &str, key: Value, xs: &Value, env: &mut Uiua) -> UiuaResult<Vec<usize>> {
+    let sig = key.signature();
+    if sig.args != 1 || sig.outputs != 1 {
+        return Err(env.error(format!(
+            "{name}'s key function must take 1 argument and return 1 value, \
+            but its signature is {sig}"
+        )));
+    }
+    let mut classes = BTreeMap::new();
+    let mut classified = Vec::with_capacity(xs.row_count());
+    for row in xs.rows() {
+        env.push(row);
+        env.call_error_on_break_with(key.clone(), || format!("break is not allowed in {name}"))?;
+        let key = env.pop(|| format!("{name}'s key function result"))?;
+        let new_class = classes.len();
+        classified.push(*classes.entry(key).or_insert(new_class));
+    }
+    Ok(classified)
+}
+
 impl Value {
     pub fn group_groups(&self, indices: &[isize], env: &Uiua) -> UiuaResult<Vec<Self>> {
         Ok(match self {
             Value::Num(arr) => arr.group_groups(indices, env)?.map(Into::into).collect(),
             Value::Byte(arr) => arr.group_groups(indices, env)?.map(Into::into).collect(),
+            Value::Complex(arr) => arr.group_groups(indices, env)?.map(Into::into).collect(),
             Value::Char(arr) => arr.group_groups(indices, env)?.map(Into::into).collect(),
             Value::Func(arr) => arr.group_groups(indices, env)?.map(Into::into).collect(),
         })
@@ -1283,6 +1885,93 @@ impl<T: ArrayValue> Array<T> {
     }
 }
 
+impl Value {
+    /// Put the rows of grouped arrays back where they came from
+    ///
+    /// This is used to implement `under` `group`.
+    pub fn ungroup(self, indices: &[isize], into: Self, env: &Uiua) -> UiuaResult<Self> {
+        let row_groups: Vec<Option<usize>> = indices
+            .iter()
+            .map(|&g| (g >= 0).then_some(g as usize))
+            .collect();
+        let group_count = row_groups.iter().flatten().max().map_or(0, |&g| g + 1);
+        self.regroup("group", &row_groups, group_count, into, env)
+    }
+    /// Put the rows of partitioned arrays back where they came from
+    ///
+    /// This is used to implement `under` `partition`.
+    pub fn unpartition(self, markers: &[isize], into: Self, env: &Uiua) -> UiuaResult<Self> {
+        let mut row_groups = Vec::with_capacity(markers.len());
+        let mut group_count = 0;
+        let mut last_marker = isize::MAX;
+        for &marker in markers {
+            if marker > 0 {
+                if marker != last_marker {
+                    group_count += 1;
+                }
+                row_groups.push(Some(group_count - 1));
+            } else {
+                row_groups.push(None);
+            }
+            last_marker = marker;
+        }
+        self.regroup("partition", &row_groups, group_count, into, env)
+    }
+    fn regroup(
+        self,
+        name: &str,
+        row_groups: &[Option<usize>],
+        group_count: usize,
+        into: Self,
+        env: &Uiua,
+    ) -> UiuaResult<Self> {
+        if row_groups.len() != into.row_count() {
+            return Err(env.error(format!(
+                "Cannot un{name} array of shape {} with indices of length {}",
+                into.format_shape(),
+                row_groups.len()
+            )));
+        }
+        if self.row_count() != group_count {
+            return Err(env.error(format!(
+                "There were {group_count} groups, but there are now {}, \
+                so the {name} cannot be inverted",
+                self.row_count()
+            )));
+        }
+        let mut groups: Vec<_> = self
+            .into_rows()
+            .map(
+                |group| match group.as_function().and_then(|f| f.as_constant()) {
+                    Some(unboxed) => unboxed.clone().into_rows(),
+                    None => group.into_rows(),
+                },
+            )
+            .collect();
+        let mut new_rows = Vec::with_capacity(row_groups.len());
+        for (g, into_row) in row_groups.iter().zip(into.into_rows()) {
+            if let Some(g) = *g {
+                let row = groups[g].next().ok_or_else(|| {
+                    env.error(format!(
+                        "Group {g} has fewer rows than it was created with, \
+                        so the {name} cannot be inverted"
+                    ))
+                })?;
+                new_rows.push(row);
+            } else {
+                new_rows.push(into_row);
+            }
+        }
+        if let Some(g) = groups.iter_mut().position(|group| group.next().is_some()) {
+            return Err(env.error(format!(
+                "Group {g} has more rows than it was created with, \
+                so the {name} cannot be inverted"
+            )));
+        }
+        Value::from_row_values(new_rows, env)
+    }
+}
+
 fn collapse_groups(
     name: &str,
     get_groups: impl Fn(&Value, &[isize], &Uiua) -> UiuaResult<Vec<Value>>,
@@ -1332,3 +2021,40 @@ fn collapse_groups(
     }
     Ok(())
 }
+
+#[test]
+fn parallel_each_matches_sequential() {
+    let code = "∵(/+⇡◿100) ⇡100000";
+    let run = |env: &mut Uiua| {
+        env.load_str(code).unwrap();
+        env.pop("result").unwrap()
+    };
+    let sequential = run(&mut Uiua::with_native_sys());
+    let parallel = run(&mut Uiua::with_native_sys().with_parallel_each(true));
+    assert_eq!(parallel.shape(), &[100000]);
+    assert_eq!(parallel, sequential);
+    // Errors come from the first failing element
+    let mut env = Uiua::with_native_sys().with_parallel_each(true);
+    let err = env.load_str("∵(⍤:<10000..) ⇡20000").unwrap_err();
+    assert!(err.to_string().contains("10000"), "{err}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_circuit_reduce_stops_early() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        fn counting_min(a: u8, b: u8) -> u8 {
+            CALLS.fetch_add(1, Ordering::Relaxed);
+            a.min(b)
+        }
+        let mut mask = vec![1u8; 10000];
+        mask[2] = 0;
+        let res = short_circuit_reduce(&mask, f64::INFINITY, 0, counting_min);
+        assert_eq!(res, Array::from(0.0));
+        assert_eq!(CALLS.load(Ordering::Relaxed), 2);
+    }
+}
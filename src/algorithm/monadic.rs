@@ -2,7 +2,7 @@
 
 use std::{
     cmp::Ordering,
-    collections::{BTreeMap, BTreeSet, HashMap},
+    collections::{BTreeMap, HashMap},
     ptr,
     sync::Arc,
 };
@@ -10,7 +10,9 @@ use std::{
 use rayon::prelude::*;
 use tinyvec::tiny_vec;
 
-use crate::{array::*, value::Value, Uiua, UiuaResult};
+use crate::{array::*, function::Function, value::Value, Uiua, UiuaResult};
+
+use super::FillContext;
 
 impl Value {
     pub fn deshape(&mut self) {
@@ -19,17 +21,84 @@ impl Value {
             Array::deshape,
             Array::deshape,
             Array::deshape,
+            Array::deshape,
         )
     }
+    /// Restore the shape of a deshaped value, erroring if its number of elements changed
+    pub fn undeshape(&mut self, shape: &Self, env: &Uiua) -> UiuaResult {
+        let shape = shape.as_naturals(env, "Shape must be a list of natural numbers")?;
+        let count: usize = shape.iter().product();
+        if self.flat_len() != count {
+            return Err(env.error(format!(
+                "Cannot undo deshape of shape {} because the number \
+                of elements changed from {count} to {}",
+                FormatShape(&shape),
+                self.flat_len()
+            )));
+        }
+        *self.shape_mut() = shape.into_iter().collect();
+        Ok(())
+    }
+    /// Deshape, unboxing boxed elements and padding them with the fill value if one is set
+    pub fn deshape_fill(mut self, env: &Uiua) -> UiuaResult<Self> {
+        if let Value::Func(funcs) = &self {
+            if (env.num_fill().is_some() || env.char_fill().is_some()) && !funcs.data.is_empty() {
+                let rows: Option<Vec<Value>> = (funcs.data.iter())
+                    .map(|f| f.as_constant().cloned())
+                    .collect();
+                if let Some(rows) = rows {
+                    return Value::from_row_values(rows, env);
+                }
+            }
+        }
+        self.deshape();
+        Ok(self)
+    }
+    /// Merge the last two axes of an array into one
+    pub fn merge(mut self, env: &Uiua) -> UiuaResult<Self> {
+        let shape = self.shape_mut();
+        if shape.len() < 2 {
+            return Err(env.error(format!(
+                "Cannot merge the last two axes of a rank {} array",
+                shape.len()
+            )));
+        }
+        let last = shape.pop().unwrap();
+        *shape.last_mut().unwrap() *= last;
+        Ok(self)
+    }
+    /// Parse a string into a number
+    ///
+    /// If the number is an integer in the range `0..256`, the result is a byte.
     pub fn parse_num(&self, env: &Uiua) -> UiuaResult<Self> {
-        Ok(self
-            .as_string(env, "Parsed array must be a string")?
-            .parse::<f64>()
-            .map_err(|e| env.error(format!("Cannot parse into number: {}", e)))?
-            .into())
+        let s = self.as_string(env, "Parsed array must be a string")?;
+        let n = s.parse::<f64>().map_err(|e| {
+            // Point out the first bad token if there are several
+            if s.split_whitespace().nth(1).is_some() {
+                let bad = s.split_whitespace().enumerate().find_map(|(i, token)| {
+                    let e = token.parse::<f64>().err()?;
+                    Some((i, token, e))
+                });
+                if let Some((i, token, e)) = bad {
+                    return env.error(format!(
+                        "Cannot parse token {i} ({token:?}) into number: {e}"
+                    ));
+                }
+            }
+            env.error(format!("Cannot parse into number: {}", e))
+        })?;
+        Ok(if is_byte(n) {
+            (n as u8).into()
+        } else {
+            n.into()
+        })
     }
 }
 
+fn is_byte(n: f64) -> bool {
+    n.fract() == 0.0 && (0.0..=255.0).contains(&n)
+}
+
 impl<T: ArrayValue> Array<T> {
     pub fn deshape(&mut self) {
         self.shape = tiny_vec![self.flat_len()];
@@ -38,6 +107,18 @@ impl<T: ArrayValue> Array<T> {
 
 impl Value {
     pub fn range(&self, env: &Uiua) -> UiuaResult<Self> {
+        if self.rank() == 0 {
+            let max = self.as_int(
+                env,
+                "Range max should be a single integer \
+                or a list of natural numbers",
+            )?;
+            if max < 0 {
+                check_range_limit(max.unsigned_abs(), env)?;
+                let data: Vec<f64> = (0..max.unsigned_abs()).map(|i| 0.0 - i as f64).collect();
+                return Ok(data.into());
+            }
+        }
         let shape = &self.as_naturals(
             env,
             "Range max should be a single natural number \
@@ -52,6 +133,16 @@ impl Value {
     }
 }
 
+fn check_range_limit(len: usize, env: &Uiua) -> UiuaResult {
+    match env.range_limit {
+        Some(limit) if len > limit => Err(env.error(format!(
+            "Range would create an array with {len} elements, \
+            which exceeds the limit of {limit}"
+        ))),
+        _ => Ok(()),
+    }
+}
+
 fn range(shape: &[usize], env: &Uiua) -> UiuaResult<Vec<f64>> {
     if shape.is_empty() {
         return Ok(vec![0.0]);
@@ -73,6 +164,7 @@ fn range(shape: &[usize], env: &Uiua) -> UiuaResult<Vec<f64>> {
         }
         len = new;
     }
+    check_range_limit(len, env)?;
     let mut data: Vec<f64> = Vec::with_capacity(len);
     let mut curr = vec![0; shape.len()];
     loop {
@@ -102,6 +194,7 @@ impl Value {
             |a| a.first(env).map(Into::into),
             |a| a.first(env).map(Into::into),
             |a| a.first(env).map(Into::into),
+            |a| a.first(env).map(Into::into),
         )
     }
     pub fn last(self, env: &Uiua) -> UiuaResult<Self> {
@@ -110,8 +203,17 @@ impl Value {
             |a| a.last(env).map(Into::into),
             |a| a.last(env).map(Into::into),
             |a| a.last(env).map(Into::into),
+            |a| a.last(env).map(Into::into),
         )
     }
+    /// Get the first row of the value, unboxing it if it is a box
+    pub fn head(self, env: &Uiua) -> UiuaResult<Self> {
+        let first = self.first(env)?;
+        Ok(match first.as_function().and_then(|f| f.as_constant()) {
+            Some(unboxed) => unboxed.clone(),
+            None => first,
+        })
+    }
 }
 
 impl<T: ArrayValue> Array<T> {
@@ -147,6 +249,7 @@ impl Value {
             Array::reverse,
             Array::reverse,
             Array::reverse,
+            Array::reverse,
         )
     }
 }
@@ -177,6 +280,7 @@ impl Value {
             Array::transpose,
             Array::transpose,
             Array::transpose,
+            Array::transpose,
         )
     }
     pub fn inv_transpose(&mut self) {
@@ -185,11 +289,96 @@ impl Value {
             Array::inv_transpose,
             Array::inv_transpose,
             Array::inv_transpose,
+            Array::inv_transpose,
         )
     }
+    /// Move an axis to the front, keeping the other axes in order
+    pub(crate) fn move_axis_to_front(&mut self, axis: usize) {
+        self.generic_mut(
+            |a| a.move_axis_to_front(axis),
+            |a| a.move_axis_to_front(axis),
+            |a| a.move_axis_to_front(axis),
+            |a| a.move_axis_to_front(axis),
+            |a| a.move_axis_to_front(axis),
+        )
+    }
+    /// Transpose a list of boxed rows into a list of boxed columns
+    ///
+    /// Short rows are padded with the fill value if one is set.
+    pub fn box_transpose(self, env: &Uiua) -> UiuaResult<Self> {
+        if self.rank() != 1 {
+            return Err(env.error(format!(
+                "Argument to boxtranspose must be a list of boxed rows, \
+                but it is rank {}",
+                self.rank()
+            )));
+        }
+        let rows: Vec<Value> = self
+            .into_rows()
+            .map(
+                |row| match row.as_function().and_then(|f| f.as_constant()) {
+                    Some(unboxed) => unboxed.clone(),
+                    None => row,
+                },
+            )
+            .collect();
+        if rows.iter().any(|row| row.rank() == 0) {
+            return Err(env.error("Cannot boxtranspose scalar rows"));
+        }
+        let max_len = rows.iter().map(Value::row_count).max().unwrap_or(0);
+        let mut columns = vec![Vec::with_capacity(rows.len()); max_len];
+        for row in rows {
+            let row = if row.row_count() < max_len {
+                let has_fill = match &row {
+                    Value::Num(_) | Value::Byte(_) => env.num_fill().is_some(),
+                    Value::Complex(_) => env.complex_fill().is_some(),
+                    Value::Char(_) => env.char_fill().is_some(),
+                    Value::Func(_) => env.func_fill().is_some(),
+                };
+                if !has_fill {
+                    return Err(env.error(format!(
+                        "Cannot boxtranspose rows of lengths {} and {} without a fill",
+                        row.row_count(),
+                        max_len
+                    )));
+                }
+                Value::from(max_len as f64).take(row, env)?
+            } else {
+                row
+            };
+            for (column, item) in columns.iter_mut().zip(row.into_rows()) {
+                column.push(item);
+            }
+        }
+        let columns = columns
+            .into_iter()
+            .map(|column| Value::from_row_values(column, env))
+            .map(|column| column.map(|column| Arc::new(Function::constant(column))))
+            .collect::<UiuaResult<Vec<_>>>()?;
+        Ok(Array::from(columns).into())
+    }
 }
 
 impl<T: ArrayValue> Array<T> {
+    /// Move an axis to the front, keeping the other axes in order
+    pub(crate) fn move_axis_to_front(&mut self, axis: usize) {
+        if axis == 0 || axis >= self.rank() {
+            return;
+        }
+        let outer: usize = self.shape[..axis].iter().product();
+        let len = self.shape[axis];
+        let inner: usize = self.shape[axis + 1..].iter().product();
+        let mut data = Vec::with_capacity(self.data.len());
+        for j in 0..len {
+            for o in 0..outer {
+                let start = (o * len + j) * inner;
+                data.extend_from_slice(&self.data[start..start + inner]);
+            }
+        }
+        self.data = data.into();
+        let len = self.shape.remove(axis);
+        self.shape.insert(0, len);
+    }
     pub fn transpose(&mut self) {
         crate::profile_function!();
         if self.shape.len() < 2 {
@@ -234,12 +423,95 @@ impl<T: ArrayValue> Array<T> {
 
 impl Value {
     pub fn rise(&self, env: &Uiua) -> UiuaResult<Self> {
-        self.generic_ref_env(Array::rise, Array::rise, Array::rise, Array::rise, env)
-            .map(Self::from_iter)
+        self.generic_ref_env(
+            Array::rise,
+            Array::rise,
+            Array::rise,
+            Array::rise,
+            Array::rise,
+            env,
+        )
+        .map(Self::from_iter)
     }
     pub fn fall(&self, env: &Uiua) -> UiuaResult<Self> {
-        self.generic_ref_env(Array::fall, Array::fall, Array::fall, Array::fall, env)
-            .map(Self::from_iter)
+        self.generic_ref_env(
+            Array::fall,
+            Array::fall,
+            Array::fall,
+            Array::fall,
+            Array::fall,
+            env,
+        )
+        .map(Self::from_iter)
+    }
+    /// Sort the rows of the value ascending
+    pub fn sort_up(&mut self) {
+        self.generic_mut(
+            Array::sort_up,
+            Array::sort_up,
+            Array::sort_up,
+            Array::sort_up,
+            Array::sort_up,
+        )
+    }
+    /// Sort the rows of the value descending
+    pub fn sort_down(&mut self) {
+        self.generic_mut(
+            Array::sort_down,
+            Array::sort_down,
+            Array::sort_down,
+            Array::sort_down,
+            Array::sort_down,
+        )
+    }
+    /// Arrange the rows of the value into a grid that is as square as possible
+    pub fn to_grid(mut self, env: &Uiua) -> UiuaResult<Self> {
+        self.generic_mut(
+            |a| a.to_grid(env),
+            |a| a.to_grid(env),
+            |a| a.to_grid(env),
+            |a| a.to_grid(env),
+            |a| a.to_grid(env),
+        )?;
+        Ok(self)
+    }
+    /// Get the index of the first minimum row
+    pub fn argmin(&self, env: &Uiua) -> UiuaResult<usize> {
+        self.generic_ref_env(
+            Array::argmin,
+            Array::argmin,
+            Array::argmin,
+            Array::argmin,
+            Array::argmin,
+            env,
+        )
+    }
+    /// Get the index of the first maximum row
+    pub fn argmax(&self, env: &Uiua) -> UiuaResult<usize> {
+        self.generic_ref_env(
+            Array::argmax,
+            Array::argmax,
+            Array::argmax,
+            Array::argmax,
+            Array::argmax,
+            env,
+        )
+    }
+    /// Check if every element is non-zero, stopping at the first zero
+    pub fn all(&self, env: &Uiua) -> UiuaResult<bool> {
+        match self {
+            Value::Num(arr) => Ok(arr.data.iter().all(|&x| x != 0.0)),
+            Value::Byte(arr) => Ok(arr.data.iter().all(|&x| x != 0)),
+            val => Err(env.error(format!("Cannot check all of {} array", val.type_name()))),
+        }
+    }
+    /// Check if any element is non-zero, stopping at the first one
+    pub fn any(&self, env: &Uiua) -> UiuaResult<bool> {
+        match self {
+            Value::Num(arr) => Ok(arr.data.iter().any(|&x| x != 0.0)),
+            Value::Byte(arr) => Ok(arr.data.iter().any(|&x| x != 0)),
+            val => Err(env.error(format!("Cannot check any of {} array", val.type_name()))),
+        }
     }
     pub fn classify(&self, env: &Uiua) -> UiuaResult<Self> {
         self.generic_ref_env(
@@ -247,16 +519,58 @@ impl Value {
             Array::classify,
             Array::classify,
             Array::classify,
+            Array::classify,
             env,
         )
         .map(Self::from_iter)
     }
+    /// Get a box of the indices of the rows in each class made by [`Value::classify`]
+    pub fn class_members(&self, env: &Uiua) -> UiuaResult<Self> {
+        let classes = self.generic_ref_env(
+            Array::classify,
+            Array::classify,
+            Array::classify,
+            Array::classify,
+            Array::classify,
+            env,
+        )?;
+        let mut members: Vec<Vec<usize>> = Vec::new();
+        for (i, class) in classes.into_iter().enumerate() {
+            if class == members.len() {
+                members.push(Vec::new());
+            }
+            members[class].push(i);
+        }
+        let members: Vec<Arc<Function>> = (members.into_iter())
+            .map(|indices| Arc::new(Function::constant(Value::from_iter(indices))))
+            .collect();
+        Ok(Array::from(members).into())
+    }
     pub fn deduplicate(&mut self) {
         self.generic_mut(
             Array::deduplicate,
             Array::deduplicate,
             Array::deduplicate,
             Array::deduplicate,
+            Array::deduplicate,
+        )
+    }
+    pub fn dedup_inverse(&mut self) -> Vec<usize> {
+        self.generic_mut(
+            Array::dedup_inverse,
+            Array::dedup_inverse,
+            Array::dedup_inverse,
+            Array::dedup_inverse,
+            Array::dedup_inverse,
+        )
+    }
+    pub fn dedup_adjacent(&mut self) {
+        self.generic_mut(
+            Array::dedup_adjacent,
+            Array::dedup_adjacent,
+            Array::dedup_adjacent,
+            Array::dedup_adjacent,
+            Array::dedup_adjacent,
         )
     }
 }
@@ -270,14 +584,7 @@ impl<T: ArrayValue> Array<T> {
             return Ok(Vec::new());
         }
         let mut indices = (0..self.row_count()).collect::<Vec<_>>();
-        indices.par_sort_by(|&a, &b| {
-            self.row_slice(a)
-                .iter()
-                .zip(self.row_slice(b))
-                .map(|(a, b)| a.array_cmp(b))
-                .find(|x| x != &Ordering::Equal)
-                .unwrap_or(Ordering::Equal)
-        });
+        indices.par_sort_by(|&a, &b| self.cmp_rows(a, b));
         Ok(indices)
     }
     pub fn fall(&self, env: &Uiua) -> UiuaResult<Vec<usize>> {
@@ -288,16 +595,107 @@ impl<T: ArrayValue> Array<T> {
             return Ok(Vec::new());
         }
         let mut indices = (0..self.row_count()).collect::<Vec<_>>();
-        indices.par_sort_by(|&a, &b| {
-            self.row_slice(a)
-                .iter()
-                .zip(self.row_slice(b))
-                .map(|(a, b)| b.array_cmp(a))
-                .find(|x| x != &Ordering::Equal)
-                .unwrap_or(Ordering::Equal)
-        });
+        indices.par_sort_by(|&a, &b| self.cmp_rows(b, a));
         Ok(indices)
     }
+    /// Compare two rows of the array lexicographically
+    fn cmp_rows(&self, a: usize, b: usize) -> Ordering {
+        (self.row_slice(a).iter())
+            .zip(self.row_slice(b))
+            .map(|(a, b)| a.array_cmp(b))
+            .find(|x| x != &Ordering::Equal)
+            .unwrap_or(Ordering::Equal)
+    }
+    /// Sort the rows of the array ascending
+    pub fn sort_up(&mut self) {
+        self.sort_rows(false)
+    }
+    /// Sort the rows of the array descending
+    pub fn sort_down(&mut self) {
+        self.sort_rows(true)
+    }
+    fn sort_rows(&mut self, descending: bool) {
+        if self.rank() == 0 || self.flat_len() == 0 {
+            return;
+        }
+        if self.rank() == 1 {
+            if descending {
+                self.data.par_sort_by(|a, b| b.array_cmp(a));
+            } else {
+                self.data.par_sort_by(|a, b| a.array_cmp(b));
+            }
+            return;
+        }
+        let mut indices = (0..self.row_count()).collect::<Vec<_>>();
+        if descending {
+            indices.par_sort_by(|&a, &b| self.cmp_rows(b, a));
+        } else {
+            indices.par_sort_by(|&a, &b| self.cmp_rows(a, b));
+        }
+        self.data = (indices.into_iter())
+            .flat_map(|i| self.row_slice(i).iter().cloned())
+            .collect();
+    }
+    /// Arrange the rows of the array into a grid that is as square as possible
+    ///
+    /// Without a fill value, the grid is the most square exact factorization of the row count.
+    /// With a fill value, the grid may be more square, and the extra cells are filled.
+    pub fn to_grid(&mut self, env: &Uiua) -> UiuaResult {
+        if self.rank() == 0 {
+            return Err(env.error("Cannot make a grid from a scalar"));
+        }
+        let n = self.row_count();
+        let (rows, cols) = if n == 0 {
+            (0, 0)
+        } else if env.fill::<T>().is_some() {
+            let mut cols = (n as f64).sqrt() as usize;
+            while cols * cols < n {
+                cols += 1;
+            }
+            (n.div_ceil(cols), cols)
+        } else {
+            let rows = (1..=(n as f64).sqrt() as usize)
+                .rev()
+                .find(|&r| n % r == 0)
+                .unwrap_or(1);
+            (rows, n / rows)
+        };
+        let target_len = rows * cols * self.row_len();
+        if self.data.len() < target_len {
+            let fill = env.fill::<T>().unwrap();
+            let start = self.data.len();
+            self.data
+                .modify(|data| data.extend(std::iter::repeat_n(fill, target_len - start)));
+        }
+        self.shape[0] = cols;
+        self.shape.insert(0, rows);
+        self.validate_shape();
+        Ok(())
+    }
+    pub fn argmin(&self, env: &Uiua) -> UiuaResult<usize> {
+        self.arg_extreme(Ordering::Less, "argmin", env)
+    }
+    pub fn argmax(&self, env: &Uiua) -> UiuaResult<usize> {
+        self.arg_extreme(Ordering::Greater, "argmax", env)
+    }
+    /// Get the index of the first row that is not beaten by any other row
+    ///
+    /// Rows are compared the same way as in [`Array::rise`].
+    fn arg_extreme(&self, better: Ordering, name: &str, env: &Uiua) -> UiuaResult<usize> {
+        if self.rank() == 0 {
+            return Err(env.error(format!("Cannot {name} a scalar")));
+        }
+        if self.row_count() == 0 {
+            return Err(env.error(format!("Cannot {name} an empty array")));
+        }
+        let mut best = 0;
+        for i in 1..self.row_count() {
+            if self.cmp_rows(i, best) == better {
+                best = i;
+            }
+        }
+        Ok(best)
+    }
     pub fn classify(&self, env: &Uiua) -> UiuaResult<Vec<usize>> {
         if self.rank() == 0 {
             return Err(env.error("Cannot classify a rank-0 array"));
@@ -312,18 +710,45 @@ impl<T: ArrayValue> Array<T> {
         Ok(classified)
     }
     pub fn deduplicate(&mut self) {
+        self.dedup_inverse();
+    }
+    /// Remove duplicate rows and get the index in the result of each original row
+    pub fn dedup_inverse(&mut self) -> Vec<usize> {
         if self.rank() == 0 {
-            return;
+            return Vec::new();
         }
         let mut deduped = Vec::new();
-        let mut seen = BTreeSet::new();
-        let mut new_len = 0;
+        let mut seen = BTreeMap::new();
+        let mut inverse = Vec::with_capacity(self.row_count());
         for row in self.rows() {
-            if seen.insert(row.clone()) {
+            let new_index = seen.len();
+            let index = *seen.entry(row).or_insert_with_key(|row| {
                 deduped.extend_from_slice(&row.data);
+                new_index
+            });
+            inverse.push(index);
+        }
+        self.data = deduped.into();
+        self.shape[0] = seen.len();
+        inverse
+    }
+    pub fn dedup_adjacent(&mut self) {
+        if self.rank() == 0 || self.row_count() == 0 {
+            return;
+        }
+        let row_len = self.row_len();
+        let mut deduped = Vec::with_capacity(self.data.len());
+        deduped.extend_from_slice(self.row_slice(0));
+        let mut new_len = 1;
+        for i in 1..self.row_count() {
+            let prev = self.row_slice(i - 1);
+            let row = self.row_slice(i);
+            if !row.iter().zip(prev).all(|(a, b)| a.array_eq(b)) {
+                deduped.extend_from_slice(row);
                 new_len += 1;
             }
         }
+        debug_assert_eq!(deduped.len(), new_len * row_len);
         self.data = deduped.into();
         self.shape[0] = new_len;
     }
@@ -351,8 +776,7 @@ impl Value {
             Self::Func(fs) => {
                 let mut befores = Vec::with_capacity(fs.row_count());
                 let mut afters = Vec::with_capacity(fs.row_count());
-                for f in fs.data {
-                    let f = Arc::try_unwrap(f).unwrap_or_else(|f| (*f).clone());
+                for f in &fs.data {
                     let (before, after) = f.under().ok_or_else(|| env.error("No inverse found"))?;
                     befores.push(before.into());
                     afters.push(after.into());
@@ -369,23 +793,36 @@ impl Value {
 
 impl Value {
     pub fn bits(&self, env: &Uiua) -> UiuaResult<Array<u8>> {
+        self.bits_ordered(false, env)
+    }
+    pub fn bits_msb(&self, env: &Uiua) -> UiuaResult<Array<u8>> {
+        self.bits_ordered(true, env)
+    }
+    fn bits_ordered(&self, msb_first: bool, env: &Uiua) -> UiuaResult<Array<u8>> {
         match self {
-            Value::Byte(n) => n.convert_ref().bits(env),
-            Value::Num(n) => n.bits(env),
+            Value::Byte(n) => n.convert_ref().bits(msb_first, env),
+            Value::Num(n) => n.bits(msb_first, env),
             _ => Err(env.error("Argument to bits must be an array of natural numbers")),
         }
     }
     pub fn inverse_bits(&self, env: &Uiua) -> UiuaResult<Array<f64>> {
+        self.inverse_bits_ordered(false, env)
+    }
+    pub fn inverse_bits_msb(&self, env: &Uiua) -> UiuaResult<Array<f64>> {
+        self.inverse_bits_ordered(true, env)
+    }
+    fn inverse_bits_ordered(&self, msb_first: bool, env: &Uiua) -> UiuaResult<Array<f64>> {
         match self {
-            Value::Byte(n) => n.inverse_bits(env),
-            Value::Num(n) => n.convert_ref_with(|n| n as u8).inverse_bits(env),
+            Value::Byte(n) => n.inverse_bits(msb_first, env),
+            Value::Num(n) => n.convert_ref_with(|n| n as u8).inverse_bits(msb_first, env),
             _ => Err(env.error("Argument to inverse_bits must be an array of naturals")),
         }
     }
 }
 
 impl Array<f64> {
-    pub fn bits(&self, env: &Uiua) -> UiuaResult<Array<u8>> {
+    /// Encode the array as bits, least significant bit first unless `msb_first` is set
+    pub fn bits(&self, msb_first: bool, env: &Uiua) -> UiuaResult<Array<u8>> {
         let mut nats = Vec::new();
         for &n in &self.data {
             if n.fract() != 0.0 {
@@ -406,9 +843,9 @@ impl Array<f64> {
             max >>= 1;
         }
         let mut new_data = Vec::with_capacity(self.data.len() * max_bits);
-        // Little endian
         for n in nats {
             for i in 0..max_bits {
+                let i = if msb_first { max_bits - 1 - i } else { i };
                 new_data.push(u8::from(n & (1 << i) != 0));
             }
         }
@@ -421,7 +858,8 @@ impl Array<f64> {
 }
 
 impl Array<u8> {
-    pub fn inverse_bits(&self, env: &Uiua) -> UiuaResult<Array<f64>> {
+    /// Decode bits into numbers, least significant bit first unless `msb_first` is set
+    pub fn inverse_bits(&self, msb_first: bool, env: &Uiua) -> UiuaResult<Array<f64>> {
         let mut bools = Vec::with_capacity(self.data.len());
         for &b in &self.data {
             if b > 1 {
@@ -435,10 +873,10 @@ impl Array<u8> {
         let mut shape = self.shape.clone();
         let bit_string_len = shape.pop().unwrap();
         let mut new_data = Vec::with_capacity(self.data.len() / bit_string_len);
-        // Little endian
         for bits in bools.chunks_exact(bit_string_len) {
             let mut n: u128 = 0;
             for (i, b) in bits.iter().enumerate() {
+                let i = if msb_first { bit_string_len - 1 - i } else { i };
                 if *b {
                     n |= 1u128.overflowing_shl(i as u32).0;
                 }
@@ -8,7 +8,7 @@ use std::{
     slice::{self, Chunks},
 };
 
-use crate::{array::*, Uiua, UiuaError, UiuaResult};
+use crate::{array::*, complex::Complex, Uiua, UiuaError, UiuaResult};
 
 use super::max_shape;
 
@@ -261,6 +261,9 @@ pub mod not {
     pub fn byte(a: u8) -> f64 {
         num(a.into())
     }
+    pub fn bool(a: u8) -> u8 {
+        1 - a
+    }
     pub fn error<T: Display>(a: T, env: &Uiua) -> UiuaError {
         env.error(format!("Cannot negate {a}"))
     }
@@ -274,6 +277,9 @@ pub mod neg {
     pub fn byte(a: u8) -> f64 {
         -f64::from(a)
     }
+    pub fn com(a: Complex) -> Complex {
+        -a
+    }
     pub fn error<T: Display>(a: T, env: &Uiua) -> UiuaError {
         env.error(format!("Cannot negate {a}"))
     }
@@ -286,6 +292,9 @@ pub mod abs {
     pub fn byte(a: u8) -> u8 {
         a
     }
+    pub fn com(a: Complex) -> f64 {
+        a.abs()
+    }
     pub fn error<T: Display>(a: T, env: &Uiua) -> UiuaError {
         env.error(format!("Cannot take the absolute value of {a}"))
     }
@@ -316,6 +325,9 @@ pub mod sqrt {
     pub fn byte(a: u8) -> f64 {
         f64::from(a).sqrt()
     }
+    pub fn com(a: Complex) -> Complex {
+        a.sqrt()
+    }
     pub fn error<T: Display>(a: T, env: &Uiua) -> UiuaError {
         env.error(format!("Cannot take the square root of {a}"))
     }
@@ -344,6 +356,39 @@ pub mod cos {
         env.error(format!("Cannot get the cosine of {a}"))
     }
 }
+/// The sine of an angle in degrees, exact at multiples of 90
+fn sin_degrees(a: f64) -> f64 {
+    match a.rem_euclid(360.0) {
+        0.0 | 180.0 => 0.0,
+        90.0 => 1.0,
+        270.0 => -1.0,
+        _ => a.to_radians().sin(),
+    }
+}
+pub mod sin_deg {
+    use super::*;
+    pub fn num(a: f64) -> f64 {
+        sin_degrees(a)
+    }
+    pub fn byte(a: u8) -> f64 {
+        sin_degrees(a.into())
+    }
+    pub fn error<T: Display>(a: T, env: &Uiua) -> UiuaError {
+        env.error(format!("Cannot get the sine of {a} degrees"))
+    }
+}
+pub mod cos_deg {
+    use super::*;
+    pub fn num(a: f64) -> f64 {
+        sin_degrees(a + 90.0)
+    }
+    pub fn byte(a: u8) -> f64 {
+        sin_degrees(f64::from(a) + 90.0)
+    }
+    pub fn error<T: Display>(a: T, env: &Uiua) -> UiuaError {
+        env.error(format!("Cannot get the cosine of {a} degrees"))
+    }
+}
 pub mod tan {
     use super::*;
     pub fn num(a: f64) -> f64 {
@@ -404,6 +449,34 @@ pub mod ceil {
         env.error(format!("Cannot get the ceiling of {a}"))
     }
 }
+pub mod trunc {
+    use super::*;
+    pub fn num(a: f64) -> f64 {
+        a.trunc()
+    }
+    pub fn byte(a: u8) -> u8 {
+        a
+    }
+    pub fn error<T: Display>(a: T, env: &Uiua) -> UiuaError {
+        env.error(format!("Cannot truncate {a}"))
+    }
+}
+pub mod round_away {
+    use super::*;
+    pub fn num(a: f64) -> f64 {
+        if a < 0.0 {
+            a.floor()
+        } else {
+            a.ceil()
+        }
+    }
+    pub fn byte(a: u8) -> u8 {
+        a
+    }
+    pub fn error<T: Display>(a: T, env: &Uiua) -> UiuaError {
+        env.error(format!("Cannot round {a} away from zero"))
+    }
+}
 pub mod round {
     use super::*;
     pub fn num(a: f64) -> f64 {
@@ -416,6 +489,30 @@ pub mod round {
         env.error(format!("Cannot get the rounded value of {a}"))
     }
 }
+pub mod is_nan {
+    use super::*;
+    pub fn num(a: f64) -> u8 {
+        a.is_nan() as u8
+    }
+    pub fn byte(_: u8) -> u8 {
+        0
+    }
+    pub fn error<T: Display>(a: T, env: &Uiua) -> UiuaError {
+        env.error(format!("Cannot check {a} for NaN"))
+    }
+}
+pub mod is_infinite {
+    use super::*;
+    pub fn num(a: f64) -> u8 {
+        a.is_infinite() as u8
+    }
+    pub fn byte(_: u8) -> u8 {
+        0
+    }
+    pub fn error<T: Display>(a: T, env: &Uiua) -> UiuaError {
+        env.error(format!("Cannot check {a} for infinity"))
+    }
+}
 
 macro_rules! cmp_impl {
     ($name:ident $eq:tt $ordering:expr) => {
@@ -439,6 +536,15 @@ macro_rules! cmp_impl {
             pub fn generic<T: Ord>(a: T, b: T) -> u8 {
                 (b.cmp(&a) $eq $ordering).into()
             }
+            pub fn com_com(a: Complex, b: Complex) -> u8 {
+                (b.array_cmp(&a) $eq $ordering) as u8
+            }
+            pub fn num_com(a: f64, b: Complex) -> u8 {
+                (b.array_cmp(&a.into()) $eq $ordering) as u8
+            }
+            pub fn com_num(a: Complex, b: f64) -> u8 {
+                (Complex::from(b).array_cmp(&a) $eq $ordering) as u8
+            }
             pub fn error<T: Display>(a: T, b: T, _env: &Uiua) -> UiuaError {
                 unreachable!("Comparisons cannot fail, failed to compare {a} and {b}")
             }
@@ -480,6 +586,15 @@ pub mod add {
     pub fn char_byte(a: char, b: u8) -> char {
         char::from_u32((b as i64 + a as i64) as u32).unwrap_or('\0')
     }
+    pub fn com_com(a: Complex, b: Complex) -> Complex {
+        b + a
+    }
+    pub fn num_com(a: f64, b: Complex) -> Complex {
+        b + a.into()
+    }
+    pub fn com_num(a: Complex, b: f64) -> Complex {
+        Complex::from(b) + a
+    }
     pub fn error<T: Display>(a: T, b: T, env: &Uiua) -> UiuaError {
         env.error(format!("Cannot add {a} and {b}"))
     }
@@ -508,6 +623,15 @@ pub mod sub {
     pub fn byte_char(a: u8, b: char) -> char {
         char::from_u32(((b as i64) - (a as i64)) as u32).unwrap_or('\0')
     }
+    pub fn com_com(a: Complex, b: Complex) -> Complex {
+        b - a
+    }
+    pub fn num_com(a: f64, b: Complex) -> Complex {
+        b - a.into()
+    }
+    pub fn com_num(a: Complex, b: f64) -> Complex {
+        Complex::from(b) - a
+    }
     pub fn error<T: Display>(a: T, b: T, env: &Uiua) -> UiuaError {
         env.error(format!("Cannot subtract {a} from {b}"))
     }
@@ -527,6 +651,15 @@ pub mod mul {
     pub fn num_byte(a: f64, b: u8) -> f64 {
         f64::from(b) * a
     }
+    pub fn com_com(a: Complex, b: Complex) -> Complex {
+        b * a
+    }
+    pub fn num_com(a: f64, b: Complex) -> Complex {
+        b * a.into()
+    }
+    pub fn com_num(a: Complex, b: f64) -> Complex {
+        Complex::from(b) * a
+    }
     pub fn error<T: Display>(a: T, b: T, env: &Uiua) -> UiuaError {
         env.error(format!("Cannot multiply {a} and {b}"))
     }
@@ -546,6 +679,15 @@ pub mod div {
     pub fn num_byte(a: f64, b: u8) -> f64 {
         f64::from(b) / a
     }
+    pub fn com_com(a: Complex, b: Complex) -> Complex {
+        b / a
+    }
+    pub fn num_com(a: f64, b: Complex) -> Complex {
+        b / a.into()
+    }
+    pub fn com_num(a: Complex, b: f64) -> Complex {
+        Complex::from(b) / a
+    }
     pub fn error<T: Display>(a: T, b: T, env: &Uiua) -> UiuaError {
         env.error(format!("Cannot divide {a} by {b}"))
     }
@@ -571,6 +713,146 @@ pub mod modulus {
     }
 }
 
+pub mod round_to {
+    use super::*;
+    pub fn num_num(a: f64, b: f64) -> f64 {
+        let decimals = a.trunc();
+        let scale = 10f64.powf(decimals.abs());
+        if !scale.is_finite() {
+            return b;
+        }
+        if decimals >= 0.0 {
+            let scaled = b * scale;
+            if scaled.is_finite() {
+                scaled.round() / scale
+            } else {
+                b
+            }
+        } else {
+            (b / scale).round() * scale
+        }
+    }
+    pub fn byte_byte(_: u8, b: u8) -> u8 {
+        b
+    }
+    pub fn byte_num(a: u8, b: f64) -> f64 {
+        num_num(a.into(), b)
+    }
+    pub fn num_byte(a: f64, b: u8) -> f64 {
+        num_num(a, b.into())
+    }
+    pub fn error<T: Display>(a: T, b: T, env: &Uiua) -> UiuaError {
+        env.error(format!("Cannot round {b} to {a} decimal places"))
+    }
+}
+
+pub mod add_sat {
+    use super::*;
+    pub fn num_num(a: f64, b: f64) -> u8 {
+        (b + a).round().clamp(0.0, 255.0) as u8
+    }
+    pub fn byte_byte(a: u8, b: u8) -> u8 {
+        b.saturating_add(a)
+    }
+    pub fn byte_num(a: u8, b: f64) -> u8 {
+        num_num(a.into(), b)
+    }
+    pub fn num_byte(a: f64, b: u8) -> u8 {
+        num_num(a, b.into())
+    }
+    pub fn error<T: Display>(a: T, b: T, env: &Uiua) -> UiuaError {
+        env.error(format!("Cannot saturating add {a} and {b}"))
+    }
+}
+
+pub mod sub_sat {
+    use super::*;
+    pub fn num_num(a: f64, b: f64) -> u8 {
+        (b - a).round().clamp(0.0, 255.0) as u8
+    }
+    pub fn byte_byte(a: u8, b: u8) -> u8 {
+        b.saturating_sub(a)
+    }
+    pub fn byte_num(a: u8, b: f64) -> u8 {
+        num_num(a.into(), b)
+    }
+    pub fn num_byte(a: f64, b: u8) -> u8 {
+        num_num(a, b.into())
+    }
+    pub fn error<T: Display>(a: T, b: T, env: &Uiua) -> UiuaError {
+        env.error(format!("Cannot saturating subtract {a} from {b}"))
+    }
+}
+
+pub mod mul_sat {
+    use super::*;
+    pub fn num_num(a: f64, b: f64) -> u8 {
+        (b * a).round().clamp(0.0, 255.0) as u8
+    }
+    pub fn byte_byte(a: u8, b: u8) -> u8 {
+        b.saturating_mul(a)
+    }
+    pub fn byte_num(a: u8, b: f64) -> u8 {
+        num_num(a.into(), b)
+    }
+    pub fn num_byte(a: f64, b: u8) -> u8 {
+        num_num(a, b.into())
+    }
+    pub fn error<T: Display>(a: T, b: T, env: &Uiua) -> UiuaError {
+        env.error(format!("Cannot saturating multiply {a} and {b}"))
+    }
+}
+
+pub mod gcd {
+    use super::*;
+    pub fn num_num(a: f64, b: f64) -> f64 {
+        let (mut a, mut b) = (a.trunc().abs(), b.trunc().abs());
+        if !a.is_finite() || !b.is_finite() {
+            return f64::NAN;
+        }
+        while b != 0.0 {
+            (a, b) = (b, a % b);
+        }
+        a
+    }
+    pub fn byte_byte(a: u8, b: u8) -> f64 {
+        num_num(a.into(), b.into())
+    }
+    pub fn byte_num(a: u8, b: f64) -> f64 {
+        num_num(a.into(), b)
+    }
+    pub fn num_byte(a: f64, b: u8) -> f64 {
+        num_num(a, b.into())
+    }
+    pub fn error<T: Display>(a: T, b: T, env: &Uiua) -> UiuaError {
+        env.error(format!("Cannot get the gcd of {a} and {b}"))
+    }
+}
+
+pub mod lcm {
+    use super::*;
+    pub fn num_num(a: f64, b: f64) -> f64 {
+        let gcd = gcd::num_num(a, b);
+        if gcd == 0.0 {
+            0.0
+        } else {
+            (a.trunc().abs() / gcd) * b.trunc().abs()
+        }
+    }
+    pub fn byte_byte(a: u8, b: u8) -> f64 {
+        num_num(a.into(), b.into())
+    }
+    pub fn byte_num(a: u8, b: f64) -> f64 {
+        num_num(a.into(), b)
+    }
+    pub fn num_byte(a: f64, b: u8) -> f64 {
+        num_num(a, b.into())
+    }
+    pub fn error<T: Display>(a: T, b: T, env: &Uiua) -> UiuaError {
+        env.error(format!("Cannot get the lcm of {a} and {b}"))
+    }
+}
+
 pub mod atan2 {
     use super::*;
     pub fn num_num(a: f64, b: f64) -> f64 {
@@ -581,6 +863,16 @@ pub mod atan2 {
     }
 }
 
+pub mod atan2_deg {
+    use super::*;
+    pub fn num_num(a: f64, b: f64) -> f64 {
+        a.atan2(b).to_degrees()
+    }
+    pub fn error<T: Display>(a: T, b: T, env: &Uiua) -> UiuaError {
+        env.error(format!("Cannot get the atan2 of {a} and {b} in degrees"))
+    }
+}
+
 pub mod pow {
     use super::*;
     pub fn num_num(a: f64, b: f64) -> f64 {
@@ -595,11 +887,48 @@ pub mod pow {
     pub fn num_byte(a: f64, b: u8) -> f64 {
         f64::from(b).powf(a)
     }
+    pub fn com_com(a: Complex, b: Complex) -> Complex {
+        b.powc(a)
+    }
+    pub fn num_com(a: f64, b: Complex) -> Complex {
+        b.powf(a)
+    }
+    pub fn com_num(a: Complex, b: f64) -> Complex {
+        Complex::from(b).powc(a)
+    }
     pub fn error<T: Display>(a: T, b: T, env: &Uiua) -> UiuaError {
         env.error(format!("Cannot get the power of {a} to {b}"))
     }
 }
 
+pub mod complex {
+    use super::*;
+    pub fn num_num(a: f64, b: f64) -> Complex {
+        Complex::new(b, a)
+    }
+    pub fn byte_byte(a: u8, b: u8) -> Complex {
+        Complex::new(b.into(), a.into())
+    }
+    pub fn byte_num(a: u8, b: f64) -> Complex {
+        Complex::new(b, a.into())
+    }
+    pub fn num_byte(a: f64, b: u8) -> Complex {
+        Complex::new(b.into(), a)
+    }
+    pub fn com_com(a: Complex, b: Complex) -> Complex {
+        b + a * Complex::I
+    }
+    pub fn num_com(a: f64, b: Complex) -> Complex {
+        b + Complex::new(0.0, a)
+    }
+    pub fn com_num(a: Complex, b: f64) -> Complex {
+        Complex::from(b) + a * Complex::I
+    }
+    pub fn error<T: Display>(a: T, b: T, env: &Uiua) -> UiuaError {
+        env.error(format!("Cannot make a complex number from {a} and {b}"))
+    }
+}
+
 pub mod log {
     use super::*;
     pub fn num_num(a: f64, b: f64) -> f64 {
@@ -622,7 +951,11 @@ pub mod log {
 pub mod max {
     use super::*;
     pub fn num_num(a: f64, b: f64) -> f64 {
-        a.max(b)
+        if a.is_nan() || b.is_nan() {
+            f64::NAN
+        } else {
+            a.max(b)
+        }
     }
     pub fn byte_byte(a: u8, b: u8) -> u8 {
         a.max(b)
@@ -644,7 +977,11 @@ pub mod max {
 pub mod min {
     use super::*;
     pub fn num_num(a: f64, b: f64) -> f64 {
-        a.min(b)
+        if a.is_nan() || b.is_nan() {
+            f64::NAN
+        } else {
+            a.min(b)
+        }
     }
     pub fn byte_byte(a: u8, b: u8) -> u8 {
         a.min(b)
@@ -8,6 +8,7 @@ use std::{
 use tinyvec::{tiny_vec, TinyVec};
 
 use crate::{
+    complex::Complex,
     cowslice::{cowslice, CowSlice},
     function::Function,
     grid_fmt::GridFmt,
@@ -407,6 +408,17 @@ impl ArrayValue for u8 {
     }
 }
 
+impl ArrayValue for Complex {
+    const NAME: &'static str = "complex";
+    fn get_fill(env: &Uiua) -> Option<Self> {
+        env.complex_fill()
+    }
+    fn array_hash<H: Hasher>(&self, hasher: &mut H) {
+        self.re.array_hash(hasher);
+        self.im.array_hash(hasher);
+    }
+}
+
 impl ArrayValue for char {
     const NAME: &'static str = "character";
     fn get_fill(env: &Uiua) -> Option<Self> {
@@ -471,6 +483,14 @@ impl ArrayCmp for u8 {
     }
 }
 
+impl ArrayCmp for Complex {
+    fn array_cmp(&self, other: &Self) -> Ordering {
+        self.re
+            .array_cmp(&other.re)
+            .then_with(|| self.im.array_cmp(&other.im))
+    }
+}
+
 impl ArrayCmp for char {
     fn array_cmp(&self, other: &Self) -> Ordering {
         self.cmp(other)
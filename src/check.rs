@@ -79,6 +79,7 @@ impl<'a> BasicValue<'a> {
             BasicValue::Arr(match value {
                 Value::Num(n) => n.data.iter().map(|n| BasicValue::Num(*n)).collect(),
                 Value::Byte(b) => b.data.iter().map(|b| BasicValue::Num(*b as f64)).collect(),
+                Value::Complex(n) => n.data.iter().map(|_| BasicValue::Other).collect(),
                 Value::Char(c) => c.data.iter().map(|_| BasicValue::Other).collect(),
                 Value::Func(f) => f
                     .data
@@ -230,6 +231,35 @@ impl<'a> VirtualEnv<'a> {
                         return Err("repeat without a number".into());
                     }
                 }
+                Do => {
+                    let body = self.pop()?;
+                    let cond = self.pop()?;
+                    let (body_sig, cond_sig) = (body.signature(), cond.signature());
+                    if cond_sig.outputs != 1 {
+                        return Err(format!(
+                            "do's condition's signature {cond_sig} does not have 1 output"
+                        ));
+                    }
+                    if body_sig.args != body_sig.outputs {
+                        return Err(format!(
+                            "do's body's signature {body_sig} does not have \
+                            as many outputs as arguments"
+                        ));
+                    }
+                    let args = body_sig.args.max(cond_sig.args);
+                    self.handle_args_outputs(args, args)?
+                }
+                Collect => {
+                    let f = self.pop()?;
+                    let sig = f.signature();
+                    if sig.outputs != sig.args + 1 {
+                        return Err(format!(
+                            "collect's function's signature {sig} does \
+                            not have 1 more output than argument"
+                        ));
+                    }
+                    self.handle_args_outputs(sig.args + 1, sig.outputs)?
+                }
                 Fold => {
                     let f = self.pop()?;
                     let sig = f.signature();
@@ -337,7 +367,7 @@ impl<'a> VirtualEnv<'a> {
                     let g = self.pop()?;
                     self.set_min_height();
                     if let BasicValue::Func(f) = f {
-                        if let Some((before, after)) = f.into_owned().under() {
+                        if let Some((before, after)) = f.under() {
                             let before_sig = before.signature();
                             let after_sig = after.signature();
                             self.handle_sig(before_sig)?;
@@ -450,6 +480,30 @@ impl<'a> VirtualEnv<'a> {
                         }
                     }
                 }
+                DupN | DropN => {
+                    let depth = match self.pop()? {
+                        BasicValue::Num(n) if n.fract() == 0.0 && n >= 0.0 => n as usize,
+                        BasicValue::Num(_) => {
+                            return Err(format!("{prim} with a non-natural depth"))
+                        }
+                        _ => return Err(format!("{prim} without a known depth")),
+                    };
+                    let mut values = Vec::with_capacity(depth + 1);
+                    for _ in 0..=depth {
+                        values.push(self.pop()?);
+                    }
+                    self.set_min_height();
+                    let x = values[depth].clone();
+                    if let DropN = prim {
+                        values.pop();
+                    }
+                    for value in values.into_iter().rev() {
+                        self.stack.push(value);
+                    }
+                    if let DupN = prim {
+                        self.stack.push(x);
+                    }
+                }
                 Join => {
                     let a = self.pop()?;
                     let b = self.pop()?;
@@ -0,0 +1,148 @@
+use std::{
+    fmt,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+/// A complex number with `f64` real and imaginary parts
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Complex {
+    pub re: f64,
+    pub im: f64,
+}
+
+impl Complex {
+    pub const ZERO: Self = Self::new(0.0, 0.0);
+    pub const ONE: Self = Self::new(1.0, 0.0);
+    pub const I: Self = Self::new(0.0, 1.0);
+    pub const fn new(re: f64, im: f64) -> Self {
+        Self { re, im }
+    }
+    /// The modulus
+    pub fn abs(self) -> f64 {
+        self.re.hypot(self.im)
+    }
+    /// The argument, in the range `(-π, π]`
+    pub fn arg(self) -> f64 {
+        self.im.atan2(self.re)
+    }
+    pub fn from_polar(r: f64, theta: f64) -> Self {
+        Self::new(r * theta.cos(), r * theta.sin())
+    }
+    /// The principal square root
+    pub fn sqrt(self) -> Self {
+        let r = self.abs();
+        let re = ((r + self.re) / 2.0).sqrt();
+        let im = ((r - self.re) / 2.0).sqrt();
+        Self::new(re, if self.im < 0.0 { -im } else { im })
+    }
+    /// The principal natural logarithm
+    pub fn ln(self) -> Self {
+        Self::new(self.abs().ln(), self.arg())
+    }
+    pub fn exp(self) -> Self {
+        Self::from_polar(self.re.exp(), self.im)
+    }
+    /// Raise to a complex power
+    ///
+    /// Small integer powers of real exponents are computed by repeated
+    /// multiplication so that, for example, `i²` is exactly `-1`.
+    pub fn powc(self, power: Self) -> Self {
+        if power.im == 0.0 && power.re.fract() == 0.0 && power.re.abs() <= 64.0 {
+            let mut acc = Self::ONE;
+            for _ in 0..power.re.abs() as usize {
+                acc = acc * self;
+            }
+            return if power.re < 0.0 { Self::ONE / acc } else { acc };
+        }
+        if self == Self::ZERO {
+            return if power == Self::ZERO {
+                Self::ONE
+            } else {
+                Self::ZERO
+            };
+        }
+        (self.ln() * power).exp()
+    }
+    pub fn powf(self, power: f64) -> Self {
+        self.powc(power.into())
+    }
+    pub fn is_nan(self) -> bool {
+        self.re.is_nan() || self.im.is_nan()
+    }
+}
+
+impl From<f64> for Complex {
+    fn from(re: f64) -> Self {
+        Self::new(re, 0.0)
+    }
+}
+
+impl From<u8> for Complex {
+    fn from(re: u8) -> Self {
+        Self::new(re as f64, 0.0)
+    }
+}
+
+impl Add for Complex {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.re + rhs.re, self.im + rhs.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.re - rhs.re, self.im - rhs.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Self::new(
+            self.re * rhs.re - self.im * rhs.im,
+            self.re * rhs.im + self.im * rhs.re,
+        )
+    }
+}
+
+impl Div for Complex {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        let denom = rhs.re * rhs.re + rhs.im * rhs.im;
+        Self::new(
+            (self.re * rhs.re + self.im * rhs.im) / denom,
+            (self.im * rhs.re - self.re * rhs.im) / denom,
+        )
+    }
+}
+
+impl Neg for Complex {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::new(-self.re, -self.im)
+    }
+}
+
+impl fmt::Display for Complex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.im < 0.0 {
+            write!(f, "{}-{}i", self.re, -self.im)
+        } else {
+            write!(f, "{}+{}i", self.re, self.im)
+        }
+    }
+}
+
+#[test]
+fn complex_arithmetic() {
+    let a = Complex::new(3.0, 4.0);
+    assert_eq!(a.abs(), 5.0);
+    assert_eq!(Complex::I * Complex::I, Complex::new(-1.0, 0.0));
+    assert_eq!(Complex::I.powf(2.0), Complex::new(-1.0, 0.0));
+    assert_eq!(Complex::new(-4.0, 0.0).sqrt(), Complex::new(0.0, 2.0));
+    assert_eq!(a / a, Complex::ONE);
+    let e = Complex::new(0.0, std::f64::consts::PI).exp() + Complex::ONE;
+    assert!(e.abs() < 1e-12);
+}
@@ -29,9 +29,6 @@ impl<T> CowSlice<T> {
     pub fn new() -> Self {
         Self::default()
     }
-    pub fn truncate(&mut self, len: usize) {
-        self.end = (self.start + len as u32).min(self.end);
-    }
 }
 
 impl<T: Clone> CowSlice<T> {
@@ -73,11 +70,43 @@ impl<T: Clone> CowSlice<T> {
             res
         }
     }
+    /// Shorten the slice without copying
+    ///
+    /// If the data is not shared, the excess items are dropped in place.
+    /// Otherwise, only the view into the shared data is shortened.
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len() {
+            return;
+        }
+        if self.data.is_unique() && self.start == 0 && self.end == self.data.len() as u32 {
+            self.data.truncate(len);
+        }
+        self.end = self.start + len as u32;
+    }
     pub fn reserve_min(&mut self, min: usize) {
         self.modify(|vec| vec.reserve(vec.capacity().max(min) - vec.len()))
     }
 }
 
+#[test]
+fn cow_slice_truncate() {
+    let mut slice = CowSlice::from([1, 2, 3, 4]);
+    let ptr = slice.as_ptr();
+    slice.truncate(3);
+    assert_eq!(slice, [1, 2, 3]);
+    assert_eq!(slice.as_ptr(), ptr);
+    // Truncated in place, so modifying does not copy
+    slice.modify(|vec| vec.push(5));
+    assert_eq!(slice, [1, 2, 3, 5]);
+    assert_eq!(slice.as_ptr(), ptr);
+
+    let shared = slice.clone();
+    slice.truncate(2);
+    assert_eq!(slice, [1, 2]);
+    assert_eq!(shared, [1, 2, 3, 5]);
+    assert_eq!(slice.as_ptr(), shared.as_ptr());
+}
+
 #[test]
 fn cow_slice_modify() {
     let mut slice = CowSlice::from([1, 2, 3]);
@@ -0,0 +1,150 @@
+//! Conversion between Uiua values and CSV
+
+use std::sync::Arc;
+
+use crate::{array::Array, function::Function, value::Value, Uiua, UiuaResult};
+
+impl Value {
+    /// Parse a CSV string into a value
+    ///
+    /// The result is a list of boxed rows, each of which is a list of boxed field strings.
+    /// Quoted fields may contain commas, newlines, and doubled quotes, as described in RFC 4180.
+    pub fn from_csv_string(csv: &str, env: &Uiua) -> UiuaResult<Self> {
+        let records = parse_csv(csv).map_err(|e| env.error(e))?;
+        let rows: Vec<Arc<Function>> = records
+            .into_iter()
+            .map(|record| {
+                let fields: Vec<Arc<Function>> = record
+                    .into_iter()
+                    .map(|field| boxed(field.chars().collect::<Vec<_>>().into()))
+                    .collect();
+                boxed(Array::from(fields).into())
+            })
+            .collect();
+        Ok(Array::from(rows).into())
+    }
+    /// Serialize a value into a CSV string
+    ///
+    /// Each row of the value is a record, and each row of a record is a field.
+    /// Rows and fields may be boxed. Fields must be strings or scalar numbers.
+    ///
+    /// This is the inverse of [`Value::from_csv_string`].
+    pub fn to_csv_string(&self, env: &Uiua) -> UiuaResult<String> {
+        let mut csv = String::new();
+        for record in self.rows() {
+            let record = unboxed(record);
+            for (i, field) in record.rows().enumerate() {
+                if i > 0 {
+                    csv.push(',');
+                }
+                let field = match unboxed(field) {
+                    Value::Char(chars) if chars.rank() <= 1 => chars.data.iter().collect(),
+                    Value::Num(nums) if nums.rank() == 0 => nums.data[0].to_string(),
+                    Value::Byte(bytes) if bytes.rank() == 0 => bytes.data[0].to_string(),
+                    field => {
+                        return Err(env.error(format!(
+                            "CSV fields must be strings or scalar numbers, \
+                            but a field is a {} array of shape {}",
+                            field.type_name(),
+                            field.format_shape()
+                        )))
+                    }
+                };
+                if field.contains([',', '"', '\r', '\n']) {
+                    csv.push('"');
+                    csv.push_str(&field.replace('"', "\"\""));
+                    csv.push('"');
+                } else {
+                    csv.push_str(&field);
+                }
+            }
+            csv.push_str("\r\n");
+        }
+        Ok(csv)
+    }
+}
+
+fn parse_csv(csv: &str) -> Result<Vec<Vec<String>>, String> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut chars = csv.chars().peekable();
+    let mut line = 1;
+    // Whether the current record has any content
+    let mut started = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if field.is_empty() => {
+                started = true;
+                let start_line = line;
+                loop {
+                    match chars.next() {
+                        Some('"') if chars.peek() == Some(&'"') => {
+                            chars.next();
+                            field.push('"');
+                        }
+                        Some('"') => break,
+                        Some(c) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            field.push(c);
+                        }
+                        None => {
+                            return Err(format!(
+                                "Unterminated quoted CSV field starting on line {start_line}"
+                            ))
+                        }
+                    }
+                }
+                match chars.peek() {
+                    None | Some(',' | '\r' | '\n') => {}
+                    Some(c) => {
+                        return Err(format!(
+                            "Unexpected character {c:?} after quoted CSV field on line {line}"
+                        ))
+                    }
+                }
+            }
+            '"' => {
+                return Err(format!(
+                    "Unexpected quote in unquoted CSV field on line {line}"
+                ))
+            }
+            ',' => {
+                started = true;
+                record.push(std::mem::take(&mut field));
+            }
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                line += 1;
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+                started = false;
+            }
+            c => {
+                started = true;
+                field.push(c);
+            }
+        }
+    }
+    if started {
+        record.push(field);
+        records.push(record);
+    }
+    Ok(records)
+}
+
+fn boxed(value: Value) -> Arc<Function> {
+    Arc::new(Function::constant(value))
+}
+
+fn unboxed(value: Value) -> Value {
+    match value {
+        Value::Func(funcs) if funcs.rank() == 0 => match funcs.data[0].as_constant() {
+            Some(value) => value.clone(),
+            None => Value::Func(funcs),
+        },
+        value => value,
+    }
+}
@@ -30,6 +30,8 @@ pub enum UiuaError {
     Throw(Box<Value>, Span),
     Break(usize, Span),
     Timeout(Span),
+    Interrupted(Span),
+    Exit(i32, Span),
     Fill(Box<Self>),
 }
 
@@ -76,6 +78,8 @@ impl fmt::Display for UiuaError {
             UiuaError::Throw(value, span) => write!(f, "{span}: {value}"),
             UiuaError::Break(_, span) => write!(f, "{span}: break outside of loop"),
             UiuaError::Timeout(_) => write!(f, "Maximum execution time exceeded"),
+            UiuaError::Interrupted(_) => write!(f, "Execution interrupted"),
+            UiuaError::Exit(status, _) => write!(f, "Program exited with status {status}"),
             UiuaError::Fill(error) => error.fmt(f),
         }
     }
@@ -107,6 +111,14 @@ impl UiuaError {
             error => Err(error),
         }
     }
+    /// Get the status code if the program was stopped by [`SysOp::Exit`](crate::SysOp::Exit)
+    pub fn exit_status(&self) -> Option<i32> {
+        match self {
+            UiuaError::Traced { error, .. } => error.exit_status(),
+            UiuaError::Exit(status, _) => Some(*status),
+            _ => None,
+        }
+    }
     /// Check if the error is fill-related
     pub(crate) fn is_fill(&self) -> bool {
         match self {
@@ -213,6 +225,14 @@ impl UiuaError {
                 kind,
                 color,
             ),
+            UiuaError::Interrupted(span) => {
+                report([("Execution interrupted", span.clone())], kind, color)
+            }
+            UiuaError::Exit(status, span) => report(
+                [(format!("Program exited with status {status}"), span.clone())],
+                kind,
+                color,
+            ),
             UiuaError::Fill(error) => error.show(color),
             UiuaError::Load(..) | UiuaError::Format(..) => self.to_string(),
         }
@@ -28,6 +28,9 @@ impl SysBackend for FormatConfigBackend {
     fn any(&self) -> &dyn Any {
         self
     }
+    fn name(&self) -> &str {
+        "format config"
+    }
 }
 
 trait ConfigValue: Sized {
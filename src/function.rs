@@ -3,7 +3,7 @@ use std::{
     fmt,
     hash::{Hash, Hasher},
     mem::{discriminant, transmute},
-    sync::Arc,
+    sync::{Arc, OnceLock},
 };
 
 use crate::{
@@ -182,6 +182,16 @@ pub struct Function {
     pub id: FunctionId,
     pub instrs: Vec<Instr>,
     signature: Signature,
+    pub(crate) cache: FunctionCache,
+}
+
+/// Lazily computed inverses of a function
+///
+/// Functions are immutable once built, so these only need to be computed once.
+#[derive(Clone, Default)]
+pub(crate) struct FunctionCache {
+    pub inverse: OnceLock<Option<Arc<Function>>>,
+    pub under: OnceLock<Option<(Arc<Function>, Arc<Function>)>>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -325,6 +335,7 @@ impl Function {
             id,
             instrs,
             signature,
+            cache: FunctionCache::default(),
         }
     }
     pub fn new_inferred(id: FunctionId, instrs: impl Into<Vec<Instr>>) -> Result<Self, String> {
@@ -334,6 +345,7 @@ impl Function {
             id,
             signature,
             instrs,
+            cache: FunctionCache::default(),
         })
     }
     pub fn into_inner(f: Arc<Self>) -> Self {
@@ -360,14 +372,57 @@ impl Function {
         }
         s
     }
+    /// Format the function as source code, starting with its signature
+    ///
+    /// For example, `(+1)` is formatted as `|1.1 +1`.
+    pub fn to_source_string(&self) -> String {
+        format!("{} {}", self.signature, self.format_inner())
+    }
     /// Get how many arguments this function pops off the stack and how many it pushes.
     /// Returns `None` if either of these values are dynamic.
     pub fn signature(&self) -> Signature {
         self.signature
     }
+    /// Get the approximate number of bytes used by the function's instructions
+    pub fn mem_size(&self) -> usize {
+        self.instrs
+            .iter()
+            .map(|instr| size_of::<Instr>() + instr.as_push().map_or(0, Value::mem_size))
+            .sum()
+    }
     pub fn is_constant(&self) -> bool {
         matches!(&*self.instrs, [Instr::Push(_)])
     }
+    /// Check if running the function can have no effect other than on the stack
+    ///
+    /// This is conservative: functions that use the system backend, random state,
+    /// threads, or control flow that escapes the function are not pure.
+    pub(crate) fn is_pure(&self) -> bool {
+        self.instrs.iter().all(|instr| match instr {
+            Instr::Push(val) => val
+                .as_func_array()
+                .into_iter()
+                .flat_map(|fs| &fs.data)
+                .all(|f| f.is_pure()),
+            Instr::Prim(prim, _) => !matches!(
+                prim,
+                Primitive::Sys(_)
+                    | Primitive::Rand
+                    | Primitive::RandArray
+                    | Primitive::Tag
+                    | Primitive::Now
+                    | Primitive::Trace
+                    | Primitive::InvTrace
+                    | Primitive::Dump
+                    | Primitive::Try
+                    | Primitive::Break
+                    | Primitive::Spawn
+                    | Primitive::Wait
+            ),
+            Instr::Dynamic(_) => false,
+            _ => true,
+        })
+    }
     pub fn constant(value: impl Into<Value>) -> Self {
         Function::new(
             FunctionId::Constant,
@@ -399,6 +454,7 @@ impl Function {
         }
     }
     pub fn as_constant_mut(&mut self) -> Option<&mut Value> {
+        self.cache = FunctionCache::default();
         match self.instrs.as_mut_slice() {
             [Instr::Push(val)] => Some(val),
             _ => None,
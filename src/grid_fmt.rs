@@ -13,6 +13,7 @@ use std::{
 
 use crate::{
     array::{Array, ArrayValue},
+    complex::Complex,
     function::Function,
     primitive::Primitive,
     value::Value,
@@ -21,11 +22,41 @@ use crate::{
 type Grid<T = char> = Vec<Vec<T>>;
 type Metagrid = Grid<Grid>;
 
+/// Parameters for pretty printing
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GridFmtParams {
+    /// Whether the value is inside a box
+    pub boxed: bool,
+    /// The maximum number of significant digits to show for numbers
+    ///
+    /// Digits before the decimal point are never dropped.
+    /// If `None`, numbers are shown in full.
+    pub precision: Option<usize>,
+}
+
+impl GridFmtParams {
+    fn unboxed(self) -> Self {
+        GridFmtParams {
+            boxed: false,
+            ..self
+        }
+    }
+    fn boxed(self) -> Self {
+        GridFmtParams {
+            boxed: true,
+            ..self
+        }
+    }
+}
+
 pub trait GridFmt {
-    fn fmt_grid(&self, boxed: bool) -> Grid;
+    fn fmt_grid(&self, params: GridFmtParams) -> Grid;
     fn grid_string(&self) -> String {
+        self.grid_string_with(GridFmtParams::default())
+    }
+    fn grid_string_with(&self, params: GridFmtParams) -> String {
         let mut s: String = self
-            .fmt_grid(false)
+            .fmt_grid(params)
             .into_iter()
             .flat_map(|v| v.into_iter().chain(once('\n')))
             .collect();
@@ -39,15 +70,15 @@ fn boxed_scalar(boxed: bool) -> impl Iterator<Item = char> {
 }
 
 impl GridFmt for u8 {
-    fn fmt_grid(&self, boxed: bool) -> Grid {
-        vec![boxed_scalar(boxed)
+    fn fmt_grid(&self, params: GridFmtParams) -> Grid {
+        vec![boxed_scalar(params.boxed)
             .chain(self.to_string().chars())
             .collect()]
     }
 }
 
 impl GridFmt for f64 {
-    fn fmt_grid(&self, boxed: bool) -> Grid {
+    fn fmt_grid(&self, params: GridFmtParams) -> Grid {
         let positive = self.abs();
         let minus = if *self < -0.0 { "¯" } else { "" };
         let s = if (positive - PI).abs() < f64::EPSILON {
@@ -58,54 +89,82 @@ impl GridFmt for f64 {
             format!("{minus}η")
         } else if positive == INFINITY {
             format!("{minus}∞")
+        } else if let Some(precision) = params.precision {
+            format!("{minus}{}", round_to_precision(positive, precision))
         } else {
             format!("{minus}{positive}")
         };
-        vec![boxed_scalar(boxed).chain(s.chars()).collect()]
+        vec![boxed_scalar(params.boxed).chain(s.chars()).collect()]
     }
 }
 
+impl GridFmt for Complex {
+    fn fmt_grid(&self, params: GridFmtParams) -> Grid {
+        let part = |n: f64| n.fmt_grid(params.unboxed()).remove(0);
+        let sign = if self.im < -0.0 { '-' } else { '+' };
+        vec![boxed_scalar(params.boxed)
+            .chain(part(self.re))
+            .chain(once(sign))
+            .chain(part(self.im.abs()))
+            .chain(once('i'))
+            .collect()]
+    }
+}
+
+/// Round a number to some number of significant digits,
+/// without dropping any digits before the decimal point
+fn round_to_precision(n: f64, precision: usize) -> f64 {
+    if n == 0.0 || !n.is_finite() {
+        return n;
+    }
+    let int_digits = n.abs().log10().floor() as i32 + 1;
+    let decimals = (precision as i32).saturating_sub(int_digits).max(0);
+    if decimals > 300 {
+        return n;
+    }
+    let factor = 10f64.powi(decimals);
+    (n * factor).round() / factor
+}
+
 impl GridFmt for char {
-    fn fmt_grid(&self, boxed: bool) -> Grid {
+    fn fmt_grid(&self, params: GridFmtParams) -> Grid {
         let formatted = format!("{self:?}");
         vec![if formatted.starts_with("'\\u{") {
-            boxed
+            params
+                .boxed
                 .then(|| Primitive::Box.glyph().unwrap())
                 .into_iter()
                 .chain(format!("+{}@\\0", *self as u32).chars())
                 .collect()
         } else {
-            once(if boxed {
-                Primitive::Box.glyph().unwrap()
-            } else {
-                '@'
-            })
-            .chain(formatted[1..formatted.len() - 1].chars())
-            .collect()
+            boxed_scalar(params.boxed)
+                .chain(once('@'))
+                .chain(formatted[1..formatted.len() - 1].chars())
+                .collect()
         }]
     }
 }
 
 impl GridFmt for Arc<Function> {
-    fn fmt_grid(&self, boxed: bool) -> Grid {
-        Function::fmt_grid(self, boxed)
+    fn fmt_grid(&self, params: GridFmtParams) -> Grid {
+        Function::fmt_grid(self, params)
     }
 }
 
 impl GridFmt for Function {
-    fn fmt_grid(&self, boxed: bool) -> Grid {
+    fn fmt_grid(&self, params: GridFmtParams) -> Grid {
         if let Some((prim, _)) = self.as_primitive() {
             return vec![prim.to_string().chars().collect()];
         }
         if let Some(value) = self.as_constant() {
-            let mut grid = value.fmt_grid(true);
-            if grid.len() == 1 && boxed {
+            let mut grid = value.fmt_grid(params.boxed());
+            if grid.len() == 1 && params.boxed {
                 grid[0].insert(0, '□');
             }
             return grid;
         }
         let mut grid: Grid = self
-            .format_inner()
+            .to_source_string()
             .lines()
             .map(|s| s.chars().collect())
             .collect();
@@ -114,7 +173,7 @@ impl GridFmt for Function {
         }
         if grid.len() == 1 {
             grid[0].insert(0, '(');
-            if boxed {
+            if params.boxed {
                 grid[0].insert(0, '□');
             }
             grid[0].push(')');
@@ -137,25 +196,32 @@ impl GridFmt for Function {
 }
 
 impl GridFmt for Value {
-    fn fmt_grid(&self, boxed: bool) -> Grid {
+    fn fmt_grid(&self, params: GridFmtParams) -> Grid {
         match self {
-            Value::Num(array) => array.fmt_grid(boxed),
-            Value::Byte(array) => array.fmt_grid(boxed),
-            Value::Char(array) => array.fmt_grid(boxed),
-            Value::Func(array) => array.fmt_grid(boxed),
+            Value::Num(array) => array.fmt_grid(params),
+            Value::Byte(array) => array.fmt_grid(params),
+            Value::Complex(array) => array.fmt_grid(params),
+            Value::Char(array) => array.fmt_grid(params),
+            Value::Func(array) => array.fmt_grid(params),
         }
     }
 }
 
 impl<T: GridFmt + ArrayValue> GridFmt for Array<T> {
-    fn fmt_grid(&self, boxed: bool) -> Grid {
+    fn fmt_grid(&self, params: GridFmtParams) -> Grid {
         if self.shape.is_empty() {
-            return self.data[0].fmt_grid(boxed);
+            return self.data[0].fmt_grid(params);
         }
         let stringy = type_name::<T>() == type_name::<char>();
+        let numeric = [
+            type_name::<f64>(),
+            type_name::<u8>(),
+            type_name::<Complex>(),
+        ]
+        .contains(&type_name::<T>());
         if *self.shape == [0] {
             return if stringy {
-                if boxed {
+                if params.boxed {
                     vec![vec!['⌜', '⌟']]
                 } else {
                     vec![vec!['"', '"']]
@@ -188,7 +254,7 @@ impl<T: GridFmt + ArrayValue> GridFmt for Array<T> {
         let mut grid: Grid = Grid::new();
 
         if !just_dims {
-            fmt_array(&self.shape, &self.data, stringy, boxed, &mut metagrid);
+            fmt_array(&self.shape, &self.data, stringy, params, &mut metagrid);
             // Determine max row heights and column widths
             let metagrid_width = metagrid.iter().map(|row| row.len()).max().unwrap();
             let metagrid_height = metagrid.len();
@@ -211,11 +277,18 @@ impl<T: GridFmt + ArrayValue> GridFmt for Array<T> {
                 column_widths[col] = max_col_width;
             }
             // Pad each metagrid cell to its row's max height and column's max width
+            let align = if stringy {
+                Align::Left
+            } else if numeric {
+                Align::Right
+            } else {
+                Align::Mixed
+            };
             for row in 0..metagrid_height {
                 let row_height = row_heights[row];
                 let mut subrows = vec![vec![]; row_height];
                 for (col_width, cell) in column_widths.iter().zip(&mut metagrid[row]) {
-                    pad_grid_center(*col_width, row_height, true, cell);
+                    pad_grid_center(*col_width, row_height, align, cell);
                     for (subrow, cell_row) in subrows.iter_mut().zip(take(cell)) {
                         subrow.extend(cell_row);
                     }
@@ -227,10 +300,30 @@ impl<T: GridFmt + ArrayValue> GridFmt for Array<T> {
             if row_count == 1 && self.rank() == 1 {
                 // Add brackets to vectors
                 if !stringy {
-                    let (left, right) = if boxed { ('⟦', '⟧') } else { ('[', ']') };
+                    let (left, right) = if params.boxed {
+                        ('⟦', '⟧')
+                    } else {
+                        ('[', ']')
+                    };
                     grid[0].insert(0, left);
                     grid[0].push(right);
                 }
+            } else if self.rank() == 1 {
+                // Add tall brackets to vectors with multiline items
+                for (i, row) in grid.iter_mut().enumerate() {
+                    let (left, right) = if i == 0 {
+                        ('⎡', '⎤')
+                    } else if i == row_count - 1 {
+                        ('⎣', '⎦')
+                    } else {
+                        ('⎢', '⎥')
+                    };
+                    row.insert(0, left);
+                    row.push(right);
+                    if params.boxed {
+                        row.insert(0, if i == 0 { '□' } else { ' ' });
+                    }
+                }
             } else {
                 // Add corners to non-vectors
                 let width = grid[0].len();
@@ -238,15 +331,16 @@ impl<T: GridFmt + ArrayValue> GridFmt for Array<T> {
                 pad_grid_center(
                     width + 4,
                     (height + 2).max(self.rank() + 1),
-                    false,
+                    Align::Center,
                     &mut grid,
                 );
-                grid[0][0] = if boxed { '╓' } else { '╭' };
+                grid[0][0] = if params.boxed { '╓' } else { '╭' };
                 grid[0][1] = '─';
                 for i in 0..self.rank().saturating_sub(1) {
-                    grid[i + 1][0] = if boxed { '║' } else { '╷' };
+                    grid[i + 1][0] = if params.boxed { '║' } else { '╷' };
                 }
-                *grid.last_mut().unwrap().last_mut().unwrap() = if boxed { '╜' } else { '╯' };
+                *grid.last_mut().unwrap().last_mut().unwrap() =
+                    if params.boxed { '╜' } else { '╯' };
                 // Handle really big grid
                 if let Some((w, _)) = term_size::dimensions() {
                     for row in grid.iter_mut() {
@@ -285,7 +379,7 @@ fn fmt_array<T: GridFmt + ArrayValue>(
     shape: &[usize],
     data: &[T],
     stringy: bool,
-    boxed: bool,
+    params: GridFmtParams,
     metagrid: &mut Metagrid,
 ) {
     if data.is_empty() {
@@ -294,7 +388,7 @@ fn fmt_array<T: GridFmt + ArrayValue>(
     }
     let rank = shape.len();
     if rank == 0 {
-        metagrid.push(vec![data[0].fmt_grid(false)]);
+        metagrid.push(vec![data[0].fmt_grid(params.unboxed())]);
         return;
     }
     if rank == 1 {
@@ -303,7 +397,7 @@ fn fmt_array<T: GridFmt + ArrayValue>(
             let mut s = String::new();
             s.extend(data.iter().map(|c| c.to_string()));
             let mut s = format!("{s:?}");
-            if boxed {
+            if params.boxed {
                 s.pop();
                 s.remove(0);
                 s.insert(0, '⌜');
@@ -311,8 +405,14 @@ fn fmt_array<T: GridFmt + ArrayValue>(
             }
             row.push(vec![s.chars().collect()]);
         } else {
-            for (i, val) in data.iter().enumerate() {
-                let mut grid = val.fmt_grid(false);
+            let grids: Vec<Grid> = data
+                .iter()
+                .map(|val| val.fmt_grid(params.unboxed()))
+                .collect();
+            // Align the tops of multiline items so nesting is easy to follow
+            let height = grids.iter().map(Vec::len).max().unwrap_or(1);
+            for (i, mut grid) in grids.into_iter().enumerate() {
+                grid.resize(height, Vec::new());
                 if i > 0 {
                     pad_grid_min(grid[0].len() + 1, grid.len(), &mut grid)
                 }
@@ -335,11 +435,21 @@ fn fmt_array<T: GridFmt + ArrayValue>(
                 metagrid.push(vec![vec![vec![' ']]; metagrid.last().unwrap().len()]);
             }
         }
-        fmt_array(shape, cell, stringy, false, metagrid);
+        fmt_array(shape, cell, stringy, params.unboxed(), metagrid);
     }
 }
 
-fn pad_grid_center(width: usize, height: usize, align_numbers: bool, grid: &mut Grid) {
+/// How to horizontally align the rows of a grid cell
+#[derive(Clone, Copy)]
+enum Align {
+    Left,
+    Center,
+    Right,
+    /// Right-align rows that end in a digit and center the rest
+    Mixed,
+}
+
+fn pad_grid_center(width: usize, height: usize, align: Align, grid: &mut Grid) {
     grid.truncate(height);
     if grid.len() < height {
         let diff = height - grid.len();
@@ -356,10 +466,11 @@ fn pad_grid_center(width: usize, height: usize, align_numbers: bool, grid: &mut
         row.truncate(width);
         if row.len() < width {
             let diff = width - row.len();
-            let post_pad = if align_numbers && row.last().map_or(false, char::is_ascii_digit) {
-                0
-            } else {
-                diff / 2
+            let post_pad = match align {
+                Align::Left => diff,
+                Align::Right => 0,
+                Align::Mixed if row.last().map_or(false, char::is_ascii_digit) => 0,
+                Align::Center | Align::Mixed => diff / 2,
             };
             let pre_pad = diff - post_pad;
             for _ in 0..pre_pad {
@@ -384,3 +495,91 @@ fn pad_grid_min(width: usize, height: usize, grid: &mut Grid) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use tinyvec::tiny_vec;
+
+    use super::*;
+
+    #[test]
+    fn numeric_columns_are_right_aligned() {
+        let array = Array::new(
+            tiny_vec![3, 3],
+            vec![1.0, -20.0, 3.0, 100.0, 5.0, f64::INFINITY, -1.0, PI, 0.5],
+        );
+        assert_eq!(
+            array.grid_string(),
+            "\
+╭─             
+╷   1 ¯20   3  
+  100   5   ∞  
+   ¯1   π 0.5  
+              ╯"
+        );
+    }
+
+    #[test]
+    fn char_rows_are_left_aligned() {
+        let array = Array::new(tiny_vec![2, 3], "a\nbcde".chars().collect::<Vec<_>>());
+        assert_eq!(
+            array.grid_string(),
+            "\
+╭─        
+╷ \"a\\nb\"  
+  \"cde\"   
+         ╯"
+        );
+    }
+
+    #[test]
+    fn precision_limits_significant_digits() {
+        let array = Array::from(vec![1.0 / 3.0, -2.0 / 3.0, 1234.5678, 0.000123456, 7.0, PI]);
+        let params = GridFmtParams {
+            precision: Some(3),
+            ..Default::default()
+        };
+        assert_eq!(
+            array.grid_string_with(params),
+            "[0.333 ¯0.667 1235 0.000123 7 π]"
+        );
+        assert_eq!(
+            array.grid_string(),
+            "[0.3333333333333333 ¯0.6666666666666666 1234.5678 0.000123456 7 π]"
+        );
+    }
+
+    #[test]
+    fn functions_show_their_signature() {
+        let mut env = crate::Uiua::with_native_sys();
+        env.load_str("{(+1) (⊂⊂) 5}").unwrap();
+        let shown = env.pop("functions").unwrap().show();
+        assert_eq!(shown, "[□(|1.1 +1) □(|3.1 ⊂⊂) □5]");
+    }
+
+    #[test]
+    fn boxed_arrays_show_nesting() {
+        let mut env = crate::Uiua::with_native_sys();
+        env.load_str(r#"{"ab" [1 2 3] @c}"#).unwrap();
+        let shown = env.pop("boxes").unwrap().show();
+        assert_eq!(shown, "[⌜ab⌟ ⟦1 2 3⟧ □@c]");
+        env.load_str(r#"{[1_2 3_4] "hi"}"#).unwrap();
+        let shown = env.pop("boxes").unwrap().show();
+        assert_eq!(
+            shown,
+            "\
+⎡╓─      ⌜hi⌟⎤
+⎢║ 1 2       ⎥
+⎢  3 4       ⎥
+⎣      ╜     ⎦"
+        );
+    }
+
+    #[test]
+    fn complex_numbers_show_both_parts() {
+        assert_eq!(Complex::new(3.0, 4.0).grid_string(), "3+4i");
+        assert_eq!(Complex::new(-1.5, -2.0).grid_string(), "¯1.5-2i");
+        let array = Array::from(vec![Complex::new(1.0, 0.0), Complex::new(0.0, -1.0)]);
+        assert_eq!(array.grid_string(), "[1+0i 0-1i]");
+    }
+}
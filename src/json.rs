@@ -0,0 +1,124 @@
+//! Conversion between Uiua values and JSON
+
+use std::sync::Arc;
+
+use serde_json::{Map, Number, Value as Json};
+use tinyvec::tiny_vec;
+
+use crate::{array::Array, function::Function, value::Value, Uiua, UiuaResult};
+
+impl Value {
+    /// Parse a JSON string into a value
+    ///
+    /// - Numbers become numbers, and booleans become `0` or `1`
+    /// - Strings become character arrays
+    /// - `null` becomes `NaN`
+    /// - Arrays whose items have the same shape become normal arrays. Otherwise, their items are boxed.
+    /// - Objects become rank `2` arrays of boxed `[key value]` pairs
+    pub fn from_json_string(json: &str, env: &Uiua) -> UiuaResult<Self> {
+        let json: Json =
+            serde_json::from_str(json).map_err(|e| env.error(format!("Cannot parse JSON: {e}")))?;
+        Ok(Self::from_json(json, env))
+    }
+    /// Serialize a value into a JSON string
+    ///
+    /// This is the inverse of [`Value::from_json_string`].
+    pub fn to_json_string(&self, env: &Uiua) -> UiuaResult<String> {
+        let json = self.to_json(env)?;
+        serde_json::to_string(&json).map_err(|e| env.error(format!("Cannot encode JSON: {e}")))
+    }
+    fn from_json(json: Json, env: &Uiua) -> Self {
+        match json {
+            Json::Null => f64::NAN.into(),
+            Json::Bool(b) => b.into(),
+            Json::Number(n) => n.as_f64().unwrap_or(f64::NAN).into(),
+            Json::String(s) => s.chars().collect::<Vec<_>>().into(),
+            Json::Array(items) => {
+                let items: Vec<Value> = items
+                    .into_iter()
+                    .map(|item| Self::from_json(item, env))
+                    .collect();
+                if items.is_empty() {
+                    return Array::<f64>::default().into();
+                }
+                let uniform = items.iter().all(|item| !matches!(item, Value::Func(_)))
+                    && items.windows(2).all(|w| w[0].shape() == w[1].shape());
+                if uniform {
+                    if let Ok(array) = Value::from_row_values(items.clone(), env) {
+                        return array;
+                    }
+                }
+                Array::from(items.into_iter().map(boxed).collect::<Vec<_>>()).into()
+            }
+            Json::Object(map) => {
+                let len = map.len();
+                let data: Vec<Arc<Function>> = map
+                    .into_iter()
+                    .flat_map(|(key, value)| {
+                        let key = Value::from(key.chars().collect::<Vec<_>>());
+                        [boxed(key), boxed(Self::from_json(value, env))]
+                    })
+                    .collect();
+                Array::new(tiny_vec![len, 2], data).into()
+            }
+        }
+    }
+    fn to_json(&self, env: &Uiua) -> UiuaResult<Json> {
+        if let Some(pairs) = self.as_json_object() {
+            let mut map = Map::with_capacity(pairs.len());
+            for (key, value) in pairs {
+                map.insert(key, value.to_json(env)?);
+            }
+            return Ok(Json::Object(map));
+        }
+        Ok(match self {
+            Value::Char(chars) if chars.rank() <= 1 => Json::String(chars.data.iter().collect()),
+            Value::Num(nums) if nums.rank() == 0 => number_to_json(nums.data[0]),
+            Value::Byte(bytes) if bytes.rank() == 0 => Json::Number(bytes.data[0].into()),
+            Value::Complex(com) if com.rank() == 0 => {
+                let c = com.data[0];
+                Json::Array(vec![number_to_json(c.re), number_to_json(c.im)])
+            }
+            Value::Func(funcs) if funcs.rank() == 0 => match funcs.data[0].as_constant() {
+                Some(value) => value.to_json(env)?,
+                None => return Err(env.error("Cannot encode a function as JSON")),
+            },
+            value => Json::Array(
+                value
+                    .rows()
+                    .map(|row| row.to_json(env))
+                    .collect::<UiuaResult<_>>()?,
+            ),
+        })
+    }
+    /// Get the `[key value]` pairs of a value that represents a JSON object
+    fn as_json_object(&self) -> Option<Vec<(String, &Value)>> {
+        let Value::Func(funcs) = self else {
+            return None;
+        };
+        if funcs.rank() != 2 || funcs.shape[1] != 2 {
+            return None;
+        }
+        let mut pairs = Vec::with_capacity(funcs.row_count());
+        for pair in funcs.data.chunks_exact(2) {
+            let key = match pair[0].as_constant()? {
+                Value::Char(key) if key.rank() <= 1 => key.data.iter().collect(),
+                _ => return None,
+            };
+            pairs.push((key, pair[1].as_constant()?));
+        }
+        Some(pairs)
+    }
+}
+
+fn boxed(value: Value) -> Arc<Function> {
+    Arc::new(Function::constant(value))
+}
+
+fn number_to_json(n: f64) -> Json {
+    if n.fract() == 0.0 && n.abs() < 2f64.powi(53) {
+        Json::Number((n as i64).into())
+    } else {
+        Number::from_f64(n).map_or(Json::Null, Json::Number)
+    }
+}
@@ -1,5 +1,6 @@
 use std::{
     cmp::Ordering,
+    collections::HashMap,
     error::Error,
     fmt,
     hash::{Hash, Hasher},
@@ -10,6 +11,15 @@ use std::{
 use crate::{primitive::Primitive, UiuaError};
 
 pub fn lex(input: &str, file: Option<&Path>) -> (Vec<Sp<Token>>, Vec<Sp<LexError>>) {
+    lex_with_aliases(input, file, &HashMap::new())
+}
+
+/// Lex the input, recognizing the given names as aliases for primitives
+pub fn lex_with_aliases(
+    input: &str,
+    file: Option<&Path>,
+    aliases: &HashMap<String, Primitive>,
+) -> (Vec<Sp<Token>>, Vec<Sp<LexError>>) {
     Lexer {
         input_chars: input.chars().collect(),
         loc: Loc {
@@ -22,6 +32,7 @@ pub fn lex(input: &str, file: Option<&Path>) -> (Vec<Sp<Token>>, Vec<Sp<LexError
         input: input.into(),
         tokens: Vec::new(),
         errors: Vec::new(),
+        aliases,
     }
     .run()
 }
@@ -385,16 +396,17 @@ impl From<Primitive> for Token {
     }
 }
 
-struct Lexer {
+struct Lexer<'a> {
     input_chars: Vec<char>,
     loc: Loc,
     file: Option<Arc<Path>>,
     input: Arc<str>,
     tokens: Vec<Sp<Token>>,
     errors: Vec<Sp<LexError>>,
+    aliases: &'a HashMap<String, Primitive>,
 }
 
-impl Lexer {
+impl<'a> Lexer<'a> {
     fn peek_char(&self) -> Option<char> {
         self.input_chars.get(self.loc.char_pos).copied()
     }
@@ -418,6 +430,26 @@ impl Lexer {
         self.update_loc(c);
         Some(c)
     }
+    /// Extend a system function name with digits and letters,
+    /// but only if doing so forms a known system function
+    fn sys_name_digits(&mut self, ident: &mut String) {
+        if !self.peek_char().is_some_and(|c| c.is_ascii_digit()) {
+            return;
+        }
+        let rest: String = self.input_chars[self.loc.char_pos..]
+            .iter()
+            .take_while(|c| c.is_ascii_alphanumeric())
+            .collect();
+        let longest = (1..=rest.len()).rev().find(|&len| {
+            Primitive::from_format_name(&format!("{ident}{}", &rest[..len])).is_some()
+        });
+        if let Some(len) = longest {
+            for c in rest[..len].chars() {
+                self.update_loc(c);
+                ident.push(c);
+            }
+        }
+    }
     fn next_char_exact(&mut self, c: char) -> bool {
         self.next_char_if(|c2| c2 == c).is_some()
     }
@@ -591,13 +623,19 @@ impl Lexer {
                     while let Some(c) = self.next_char_if(is_ident_char) {
                         ident.push(c);
                     }
+                    // System functions may contain digits, like `&b64en`
+                    if ident.starts_with('&') {
+                        self.sys_name_digits(&mut ident);
+                    }
                     // Try to parse as primitives
                     let lowercase_end = ident
                         .char_indices()
                         .find(|(_, c)| c.is_ascii_uppercase())
                         .map_or(ident.len(), |(i, _)| i);
-                    if let Some(prims) = Primitive::from_format_name_multi(&ident[..lowercase_end])
-                    {
+                    if let Some(prims) = Primitive::from_format_name_multi_with_aliases(
+                        &ident[..lowercase_end],
+                        self.aliases,
+                    ) {
                         let mut start = start;
                         for (prim, frag) in prims {
                             let end = Loc {
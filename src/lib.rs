@@ -12,11 +12,15 @@ pub mod array;
 pub mod ast;
 mod check;
 mod compile;
+pub mod complex;
 mod cowslice;
+mod csv;
 mod error;
 pub mod format;
 pub mod function;
 mod grid_fmt;
+#[cfg(feature = "json")]
+mod json;
 pub mod lex;
 pub mod lsp;
 pub mod parse;
@@ -29,7 +33,12 @@ pub mod value;
 
 use std::sync::Arc;
 
-pub use {error::*, run::Uiua, sys::*};
+pub use {
+    error::*,
+    run::{ExecutionLimits, Uiua},
+    sys::*,
+    value::ArrayBuilder,
+};
 
 pub type Ident = Arc<str>;
 
@@ -48,3 +57,157 @@ fn suite() {
         }
     }
 }
+
+#[test]
+fn recursion_limit() {
+    let mut env = Uiua::with_native_sys().with_recursion_limit(5);
+    let err = env.load_str("F ← |1 ↬1\nF 0").unwrap_err();
+    assert!(
+        err.to_string().contains("Recursion limit exceeded"),
+        "{err}"
+    );
+    let mut env = Uiua::with_native_sys().with_recursion_limit(5);
+    env.load_str("F ← |1 ↬1\n⍣F⋅⋅0 0").unwrap();
+    // The default limit is reached before a thread with the recommended stack overflows
+    let err = std::thread::Builder::new()
+        .stack_size(run::DEFAULT_RECURSION_STACK_SIZE)
+        .spawn(|| {
+            let mut env = Uiua::with_native_sys();
+            env.load_str("F ← |1 ↬1\nF 0").unwrap_err()
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+    assert!(
+        err.to_string().contains("Recursion limit exceeded"),
+        "{err}"
+    );
+}
+
+#[test]
+fn step_budget() {
+    for code in [
+        "⍥(+1)∞ 0",
+        "⍥()∞",
+        "⍣(⍥(+1)∞)⋅(⍥(+1)∞) 0",
+        "⍥(⍣(⍥(+1)∞)⋅⋅0 0)∞",
+        "do(+1)(1) 0",
+    ] {
+        let mut env = Uiua::with_native_sys().with_step_budget(1000);
+        let err = env.load_str(code).unwrap_err();
+        assert!(
+            err.to_string().contains("Execution budget exceeded"),
+            "{err}"
+        );
+    }
+    let mut env = Uiua::with_native_sys().with_step_budget(1000);
+    env.load_str("⍥(+1)10 0").unwrap();
+    // The first budget error can be caught
+    let mut env = Uiua::with_native_sys().with_step_budget(1000);
+    env.load_str("⍣(⍥(+1)∞)⋅⋅5 0").unwrap();
+    assert_eq!(env.pop("handler result").unwrap(), value::Value::from(5.0));
+    // Steps are counted across loads
+    let mut env = Uiua::with_native_sys().with_step_budget(1000);
+    let loads = (0..10)
+        .take_while(|_| env.load_str("⍥(+1)300 0").is_ok())
+        .count();
+    assert!(loads < 10, "{loads}");
+}
+
+#[test]
+fn execution_limits() {
+    use std::sync::atomic::Ordering;
+    let limits = || ExecutionLimits {
+        max_call_depth: 5,
+        max_steps: Some(1000),
+        max_range_size: Some(100),
+        ..Default::default()
+    };
+    for (code, message) in [
+        ("F ← |1 ↬1\nF 0", "Recursion limit exceeded"),
+        ("⍥(+1)∞ 0", "Execution budget exceeded"),
+        ("⇡1000", "exceeds the limit of 100"),
+        ("⇡¯1000", "exceeds the limit of 100"),
+        ("⇡10_10_10", "exceeds the limit of 100"),
+    ] {
+        let mut env = Uiua::with_native_sys().with_limits(limits());
+        let err = env.load_str(code).unwrap_err();
+        assert!(err.to_string().contains(message), "{err}");
+    }
+    // Errors under the limits can be caught
+    let mut env = Uiua::with_native_sys().with_limits(limits());
+    env.load_str("F ← |1 ↬1\n⍣F⋅⋅0 0").unwrap();
+    env.load_str("⍣⇡⋅⋅0 1000").unwrap();
+    env.load_str("⍣(⍥(+1)∞)⋅⋅0 0").unwrap();
+    env.load_str("⇡100").unwrap();
+    // The interrupt flag is the one from the limits
+    let limits = limits();
+    let interrupt = limits.interrupt.clone();
+    let mut env = Uiua::with_native_sys().with_limits(limits);
+    interrupt.store(true, Ordering::Relaxed);
+    let err = env.load_str("⍥(+1)10 0").unwrap_err();
+    assert!(err.to_string().contains("Execution interrupted"), "{err}");
+}
+
+#[test]
+fn interrupt() {
+    use std::sync::atomic::Ordering;
+    let mut env = Uiua::with_native_sys();
+    let handle = env.interrupt_handle();
+    let setter = std::thread::spawn(move || {
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        handle.store(true, Ordering::Relaxed);
+    });
+    let err = env.load_str("⍥(+1)∞ 0").unwrap_err();
+    setter.join().unwrap();
+    assert!(err.to_string().contains("Execution interrupted"), "{err}");
+    env.interrupt_handle().store(false, Ordering::Relaxed);
+    env.load_str("⍥(+1)10 0").unwrap();
+}
+
+#[test]
+fn get_binding() {
+    let mut env = Uiua::with_native_sys();
+    env.load_str("Double ← ×2\nX ← 5").unwrap();
+    assert_eq!(env.get_binding("X"), Some(value::Value::from(5.0)));
+    assert_eq!(env.get_binding("Y"), None);
+    let double = env.get_binding("Double").unwrap();
+    env.push(21.0);
+    env.call(double).unwrap();
+    assert_eq!(env.pop_typed::<f64>("result").unwrap(), 42.0);
+    env.load_str("Double 4").unwrap();
+    assert_eq!(env.pop_typed::<f64>("result").unwrap(), 8.0);
+}
+
+#[test]
+fn pop_typed() {
+    let mut env = Uiua::with_native_sys();
+    env.load_str("[1.5 2] ⇡4 ≠0 [1 0 1] \"hello\"").unwrap();
+    assert_eq!(env.pop_typed::<Vec<f64>>("floats").unwrap(), [1.5, 2.0]);
+    assert_eq!(env.pop_typed::<Vec<usize>>("range").unwrap(), [0, 1, 2, 3]);
+    let bytes = env.pop("bytes").unwrap();
+    assert!(matches!(bytes, value::Value::Byte(_)));
+    assert_eq!(bytes.as_num_vec(), Some(vec![1.0, 0.0, 1.0]));
+    assert_eq!(bytes.as_nat_vec(), Some(vec![1, 0, 1]));
+    assert_eq!(env.pop_typed::<String>("string").unwrap(), "hello");
+    assert!(env.pop_typed::<f64>("missing").is_err());
+    env.push("not a number");
+    assert!(env.pop_typed::<f64>("string").is_err());
+}
+
+#[test]
+fn primitive_aliases() {
+    use primitive::Primitive;
+    let mut env = Uiua::with_native_sys().with_primitive_alias("sq", Primitive::Sqrt);
+    env.load_str("sq 9 sqsq 16").unwrap();
+    assert_eq!(env.pop_typed::<f64>("sq").unwrap(), 3.0);
+    assert_eq!(env.pop_typed::<f64>("sqsq").unwrap(), 2.0);
+    // Without the alias, `sq` is not a primitive
+    let mut env = Uiua::with_native_sys();
+    assert!(env.load_str("sq 9").is_err());
+    // Aliases may be a single character
+    let mut env = Uiua::with_native_sys().with_primitive_alias("q", Primitive::Sqrt);
+    env.load_str("q 9 qneg ¯16").unwrap();
+    assert_eq!(env.pop_typed::<f64>("q").unwrap(), 3.0);
+    assert_eq!(env.pop_typed::<f64>("qneg").unwrap(), 4.0);
+}
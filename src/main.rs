@@ -7,7 +7,7 @@ use std::{
     path::{Path, PathBuf},
     process::{exit, Child, Command, Stdio},
     sync::mpsc::channel,
-    thread::sleep,
+    thread::{self, sleep},
     time::Duration,
 };
 
@@ -19,7 +19,7 @@ use once_cell::sync::Lazy;
 use parking_lot::Mutex;
 use uiua::{
     format::{format_file, FormatConfig, FormatConfigSource},
-    run::RunMode,
+    run::{RunMode, DEFAULT_RECURSION_STACK_SIZE},
     Uiua, UiuaError, UiuaResult,
 };
 
@@ -41,7 +41,18 @@ fn main() {
         }
     });
 
-    if let Err(e) = run() {
+    // Deep recursion needs more stack than the main thread has
+    let res = thread::Builder::new()
+        .stack_size(DEFAULT_RECURSION_STACK_SIZE)
+        .spawn(run)
+        .unwrap()
+        .join()
+        .unwrap_or_else(|e| std::panic::resume_unwind(e));
+    uiua::close_native_streams();
+    if let Err(e) = res {
+        if let Some(status) = e.exit_status() {
+            exit(status);
+        }
         println!("{}", e.show(true));
         exit(1);
     }
@@ -1,4 +1,4 @@
-use std::{error::Error, fmt, path::Path};
+use std::{collections::HashMap, error::Error, fmt, path::Path};
 
 use crate::{
     ast::*,
@@ -90,7 +90,16 @@ pub fn parse(
     input: &str,
     path: Option<&Path>,
 ) -> (Vec<Item>, Vec<Sp<ParseError>>, Vec<Diagnostic>) {
-    let (tokens, lex_errors) = lex(input, path);
+    parse_with_aliases(input, path, &HashMap::new())
+}
+
+/// Parse the input, recognizing the given names as aliases for primitives
+pub fn parse_with_aliases(
+    input: &str,
+    path: Option<&Path>,
+    aliases: &HashMap<String, Primitive>,
+) -> (Vec<Item>, Vec<Sp<ParseError>>, Vec<Diagnostic>) {
+    let (tokens, lex_errors) = lex_with_aliases(input, path, aliases);
     let errors = lex_errors
         .into_iter()
         .map(|e| e.map(ParseError::Lex))
@@ -206,6 +206,27 @@ primitive!(
     ///
     /// See also: [roll]
     (3(3), Unroll, Stack, ("unroll", '↶')),
+    /// Duplicate the value at some depth in the stack to the top
+    ///
+    /// The depth is a natural number. A depth of `0` is the top of the stack.
+    /// ex: [dupn 2 1 2 3]
+    ///
+    /// [dupn]`0` is the same as [duplicate], and [dupn]`1` is the same as [over].
+    /// ex: [dupn 0 1 2]
+    /// ex: [dupn 1 1 2]
+    ///
+    /// See also: [dropn]
+    ((None), DupN, Stack, "dupn"),
+    /// Remove the value at some depth in the stack
+    ///
+    /// The depth is a natural number. A depth of `0` is the top of the stack.
+    /// ex: [dropn 2 1 2 3]
+    ///
+    /// [dropn]`0` is the same as [pop].
+    /// ex: [dropn 0 1 2 3]
+    ///
+    /// See also: [dupn]
+    ((None), DropN, Stack, "dropn"),
     // Pervasive monadic ops
     /// Logical not
     ///
@@ -224,6 +245,8 @@ primitive!(
     /// ex: ± ¯5
     /// ex: ± 0
     /// ex: ± [¯2 ¯1 0 1 2]
+    /// The sign of `NaN` is `NaN`.
+    /// ex: ± NaN
     (1, Sign, MonadicPervasive, ("sign", '±')),
     /// Negate a number
     ///
@@ -232,6 +255,8 @@ primitive!(
     /// ex: ¯ 1
     /// ex: ¯ ¯3
     /// ex: ¯ [1 2 ¯3]
+    /// Like other monadic pervasive functions, [negate] works on the contents of [box]ed arrays.
+    /// ex: ¯ {1 2_3 [4_5 6_7]}
     (
         1,
         Neg,
@@ -273,6 +298,20 @@ primitive!(
     (1, Asin, MonadicPervasive),
     /// Get the arccosine of a number
     (1, Acos, MonadicPervasive),
+    /// Get the sine of an angle in degrees
+    ///
+    /// ex: sindeg 90
+    /// ex: sindeg [0 45 90 180 270]
+    ///
+    /// See also: [sine], [cosdeg]
+    (1, SinDeg, MonadicPervasive, "sindeg"),
+    /// Get the cosine of an angle in degrees
+    ///
+    /// ex: cosdeg 180
+    /// ex: cosdeg [0 90 180 360]
+    ///
+    /// See also: [sindeg]
+    (1, CosDeg, MonadicPervasive, "cosdeg"),
     /// Round to the nearest integer towards `¯∞`
     ///
     /// ex: ⌊1.5
@@ -285,6 +324,20 @@ primitive!(
     /// ex: ⌈¯1.5
     /// ex: ⌈[1.5 ¯1.5 0.5 ¯0.5]
     (1, Ceil, MonadicPervasive, ("ceiling", '⌈')),
+    /// Round to the nearest integer towards `0`
+    ///
+    /// This is the same as [floor] for positive numbers and [ceiling] for negative numbers.
+    /// ex: trunc 1.5
+    /// ex: trunc ¯1.5
+    /// ex: trunc [1.5 ¯1.5 0.5 ¯0.5]
+    (1, Trunc, MonadicPervasive, "trunc"),
+    /// Round to the nearest integer away from `0`
+    ///
+    /// This is the same as [ceiling] for positive numbers and [floor] for negative numbers.
+    /// ex: roundaway 1.2
+    /// ex: roundaway ¯1.2
+    /// ex: roundaway [1.5 ¯1.5 0.5 ¯0.5]
+    (1, RoundAway, MonadicPervasive, "roundaway"),
     /// Round to the nearest integer
     ///
     /// ex: ⁅1.2
@@ -292,6 +345,18 @@ primitive!(
     /// ex: ⁅1.5
     /// ex: ⁅[0.1 π 2 9.9 7.5]
     (1, Round, MonadicPervasive, ("round", '⁅')),
+    /// Check which elements of an array are `NaN`
+    ///
+    /// ex: isnan [1 NaN 3]
+    /// ex: isnan ÷0 [0 1 ¯1]
+    /// Only numbers can be `NaN`.
+    /// ex! isnan "abc"
+    (1, IsNaN, MonadicPervasive, "isnan"),
+    /// Check which elements of an array are infinite
+    ///
+    /// ex: isinfinite [1 ∞ ¯∞ NaN]
+    /// ex: isinfinite ÷0 [0 1 ¯1]
+    (1, IsInfinite, MonadicPervasive, "isinfinite"),
     /// Compare for equality
     ///
     /// ex: =1 2
@@ -436,6 +501,11 @@ primitive!(
     /// ex: ↧ 3 5
     /// ex: ↧ [1 4 2] [3 7 1]
     ///
+    /// If either number is `NaN`, the result is `NaN`. This means that [reduce][minimum] of an array containing `NaN` is `NaN`.
+    /// ex: /↧ [3 NaN 1]
+    /// To ignore `NaN`s instead, remove them first with [keep] and [isnan].
+    /// ex: /↧ ▽¬isnan. [3 NaN 1]
+    ///
     /// Uiua does not have dedicated boolean logical operators.
     /// [minimum] can be used as a logical AND.
     /// ex: ≥5∶≤8. [6 2 5 9 6 5 0 4]
@@ -446,6 +516,11 @@ primitive!(
     /// ex: ↥ 3 5
     /// ex: ↥ [1 4 2] [3 7 1]
     ///
+    /// If either number is `NaN`, the result is `NaN`. This means that [reduce][maximum] of an array containing `NaN` is `NaN`.
+    /// ex: /↥ [3 NaN 1]
+    /// To ignore `NaN`s instead, remove them first with [keep] and [isnan].
+    /// ex: /↥ ▽¬isnan. [3 NaN 1]
+    ///
     /// Uiua does not have dedicated boolean logical operators.
     /// [maximum] can be used as a logical OR.
     /// ex: ↥,,≤5∶≥8. [6 2 5 9 6 5 0 4]
@@ -457,6 +532,75 @@ primitive!(
     /// ex: ∠ ¯1 0
     /// ex: ∠ √2 √2
     (2, Atan, DyadicPervasive, ("atangent", '∠')),
+    /// Take the arctangent of two numbers in degrees
+    ///
+    /// Like [atangent], but the angle is in degrees in the range `(-180, 180]`.
+    /// ex: atandeg 1 0
+    /// ex: atandeg 1 1
+    (2, AtanDeg, DyadicPervasive, "atandeg"),
+    /// Round to a number of decimal places
+    ///
+    /// The first value is the number of decimal places, and the second value is the number to round.
+    /// ex: roundto 2 π
+    /// ex: roundto 1 [1.23 4.56 7.89]
+    /// A negative number of decimal places rounds to tens, hundreds, and so on.
+    /// ex: roundto ¯2 1234.5
+    /// Like [round], ties are rounded away from `0`.
+    /// ex: roundto 1 [0.25 ¯0.25]
+    /// ex: roundto 0 [0.5 1.5 2.5 ¯2.5]
+    /// If the number of decimal places is too large in either direction to compute, the number is unchanged.
+    /// ex: roundto 400 1.5
+    (2, RoundTo, DyadicPervasive, "roundto"),
+    /// Add two arrays, clamping the result to the range of a byte
+    ///
+    /// The result is always a byte array, rounded and clamped to between `0` and `255`.
+    /// This is useful for blending images.
+    /// ex: addsat 100 200
+    /// ex: addsat 10 [100 200 250]
+    (2, AddSat, DyadicPervasive, "addsat"),
+    /// Subtract two arrays, clamping the result to the range of a byte
+    ///
+    /// Like [subtract], the first argument is subtracted from the second.
+    /// The result is always a byte array, rounded and clamped to between `0` and `255`.
+    /// ex: subsat 50 10
+    /// ex: subsat 100 [50 150 250]
+    (2, SubSat, DyadicPervasive, "subsat"),
+    /// Multiply two arrays, clamping the result to the range of a byte
+    ///
+    /// The result is always a byte array, rounded and clamped to between `0` and `255`.
+    /// ex: mulsat 20 20
+    /// ex: mulsat 2 [50 100 150]
+    (2, MulSat, DyadicPervasive, "mulsat"),
+    /// Get the greatest common divisor of two arrays
+    ///
+    /// Only the integer parts of the numbers are used, and their signs are ignored.
+    /// ex: gcd 12 18
+    /// ex: gcd 12 [8 9 10 11]
+    /// ex: gcd ¯4 6
+    /// The [gcd] of `0` and a number is that number.
+    /// ex: gcd 0 7
+    (2, Gcd, DyadicPervasive, ("gcd", '∨')),
+    /// Get the least common multiple of two arrays
+    ///
+    /// Only the integer parts of the numbers are used, and their signs are ignored.
+    /// ex: lcm 4 6
+    /// ex: lcm 3 [1 2 3 4 5]
+    /// If either number is `0`, the result is `0`.
+    /// ex: lcm 0 7
+    (2, Lcm, DyadicPervasive, ("lcm", '⩓')),
+    /// Make a complex number
+    ///
+    /// The first argument is the imaginary part, and the second argument is the real part.
+    /// ex: complex 3 5
+    /// ex: complex [0 1 2] [3 4 5]
+    /// Complex numbers work with [add], [subtract], [multiply], [divide], and [power].
+    /// ex: × . complex 1 0
+    /// ex: + 2 complex 3 4
+    /// [sqrt] of a complex number gives the principal root.
+    /// ex: √ complex 0 ¯4
+    /// [absolute value] gives the magnitude.
+    /// ex: ⌵ complex 4 3
+    (2, Complex, DyadicPervasive, "complex"),
     /// Get the number of rows in an array
     ///
     /// ex: ⧻5
@@ -488,6 +632,9 @@ primitive!(
     ///   :    △[1_2_3 4_5_6]
     ///   :   ⇡△[1_2_3 4_5_6]
     ///   : ⊡⇡△.[1_2_3 4_5_6]
+    ///
+    /// If the input is a negative scalar, the range counts down from `0`, stopping before the input.
+    /// ex: ⇡¯5
     (1, Range, MonadicArray, ("range", '⇡')),
     /// Get the first row of an array
     ///
@@ -496,6 +643,14 @@ primitive!(
     /// ex! ⊢[]
     /// ex! ⊢1
     (1, First, MonadicArray, ("first", '⊢')),
+    /// Get the first row of an array, unboxing it if it is a box
+    ///
+    /// This is like [first] followed by [unbox].
+    ///
+    /// ex: head {"Hello" "World"}
+    /// ex: head [1_2 3_4]
+    /// ex! head {}
+    (1, Head, MonadicArray, "head"),
     /// Get the last element of an array
     (1, Last, MonadicArray),
     /// Reverse the rows of an array
@@ -511,10 +666,35 @@ primitive!(
     ///
     /// It looks like `♭` because it *flat*tens the array.
     ///
+    /// If a [fill] value is set, the elements of a [box]ed array are unboxed and padded with it to form a rectangular array.
+    /// ex: ⬚0♭{[1 2] [3] [4 5 6]}
+    /// ex: ⬚@ ♭{"ab" "c" "def"}
+    ///
+    /// [under][deshape] flattens an array, runs a function on the elements, and restores the original shape.
+    /// The function must not change the number of elements.
+    /// ex: ⍜♭⇌ [1_2_3 4_5_6]
+    /// ex! ⍜♭(↘1) [1_2_3 4_5_6]
+    ///
     /// See also: [reshape]
     (1, Deshape, MonadicArray, ("deshape", '♭')),
-    /// Encode an array as bits (big-endian)
+    /// End step of under deshape
+    (2, Undeshape, Misc),
+    /// Merge the last two axes of an array into one
+    ///
+    /// An array with shape `[n h w]` becomes an array with shape `[n h×w]`.
+    /// ex: merge ↯2_3_4⇡24
+    /// ex: △merge ↯2_3_4⇡24
+    /// Unlike [deshape], the leading axes are kept.
+    /// ex: △♭ ↯2_3_4⇡24
+    ///
+    /// [under][merge] lets you work on each slice as a list.
+    /// ex: ⍜merge(≡⇌) ↯2_2_3⇡12
+    ///
+    /// See also: [split]
+    (1, Merge, MonadicArray, "merge"),
+    /// Encode an array as bits (little-endian)
     ///
+    /// The least significant bit comes first.
     /// The result will always be 1 rank higher than the input.
     /// ex: ⋯27
     /// ex: ⋯⇡8
@@ -529,6 +709,17 @@ primitive!(
     (1, Bits, MonadicArray, ("bits", '⋯')),
     /// Inverse of Bits
     (1, InverseBits, MonadicArray),
+    /// Encode an array as bits (big-endian)
+    ///
+    /// Like [bits], but the most significant bit comes first.
+    /// ex: bitsmsb 6
+    /// ex: bitsmsb [1 2 3]
+    ///
+    /// [invert][bitsmsb] decodes the bits in the same order.
+    /// ex: ⍘bitsmsb [1 1 0]
+    (1, BitsMsb, MonadicArray, "bitsmsb"),
+    /// Inverse of BitsMsb
+    (1, InverseBitsMsb, MonadicArray),
     /// Rotate the shape of an array
     ///
     /// ex: ⍉.[1_2 3_4 5_6]
@@ -538,9 +729,39 @@ primitive!(
     /// ex: [1_2 3_4 5_6]
     ///   : ↻1△ .
     ///   : △⍉  ∶
+    ///
+    /// [under][transpose] applies a function to the columns of an array as if they were rows.
+    /// ex: ⍜⍉(⍜⊢¯) [1_2 3_4]
+    /// ex: ⍜⍉(≡⇌) [1_2_3 4_5_6]
+    /// ex: ⍜⍉(↘1) [1_2_3 4_5_6]
+    /// The function should not change the rank of the array. If it does, the axes of the result will not line up with the original ones.
     (1, Transpose, MonadicArray, ("transpose", '⍉')),
     /// Inverse of Transpose
     (1, InvTranspose, MonadicArray),
+    /// Transpose a list of [box]ed rows into a list of [box]ed columns
+    ///
+    /// ex: boxtranspose {[1 2 3] [4 5 6]}
+    /// This is useful for tables whose columns have different types.
+    /// ex: boxtranspose {{1 "one"} {2 "two"} {3 "three"}}
+    ///
+    /// Rows of different lengths are padded with the [fill] value.
+    /// ex: ⬚0boxtranspose {1_2_3 4_5}
+    /// Without a fill, ragged rows are an error.
+    /// ex! boxtranspose {1_2_3 4_5}
+    (1, BoxTranspose, MonadicArray, "boxtranspose"),
+    /// Arrange the rows of an array into a grid that is as square as possible
+    ///
+    /// This is useful for quickly visualizing a long list.
+    /// The grid's shape is the most square pair of factors of the [length].
+    /// ex: togrid ⇡12
+    /// ex: togrid ⇡10
+    /// If a [fill] value is set, the grid may be more square, and extra cells are filled.
+    /// ex: ⬚0togrid ⇡10
+    /// Higher rank arrays have their rows arranged.
+    /// ex: △togrid ↯6_2 0
+    ///
+    /// See also: [reshape]
+    (1, ToGrid, MonadicArray, "togrid"),
     /// Get the indices into an array if it were sorted ascending
     ///
     /// The [rise] of an array is the list of indices that would sort the array ascending if used with [select].
@@ -563,6 +784,73 @@ primitive!(
     /// Here, we sort the array descending by the [absolute value] of its elements.
     /// ex: ⊏⍖⌵.6_2_7_0_¯1_5
     (1, Fall, MonadicArray, ("fall", '⍖')),
+    /// Sort the rows of an array ascending
+    ///
+    /// ex: sort [6 2 7 0 ¯1 5]
+    /// ex: sort "hello"
+    /// Rows are compared the same way as in [rise], so multidimensional arrays are sorted by whole rows.
+    /// ex: sort [3_1 1_4 3_0 1_2]
+    /// [sort] is equivalent to [select] of the [rise].
+    /// ex: ⊏⍏. [6 2 7 0 ¯1 5]
+    ///
+    /// [sort] is not invertible, because the original order of the rows is lost.
+    /// To put the rows back in their original places after working on them in sorted order, use [under][select] with the [rise] instead.
+    /// ex: ⍜⊏(\+)⍏. [6 2 7 0]
+    (1, Sort, MonadicArray, "sort"),
+    /// Sort the rows of an array descending
+    ///
+    /// ex: sortdown [6 2 7 0 ¯1 5]
+    /// Rows are compared the same way as in [fall].
+    /// ex: sortdown [3_1 1_4 3_0 1_2]
+    /// [sortdown] is equivalent to [select] of the [fall].
+    /// ex: ⊏⍖. [6 2 7 0 ¯1 5]
+    ///
+    /// Like [sort], [sortdown] is not invertible.
+    (1, SortDown, MonadicArray, "sortdown"),
+    /// Get the index of the minimum row of an array
+    ///
+    /// Rows are compared the same way as in [rise].
+    /// If there are several minimum rows, the index of the first one is returned.
+    /// ex: argmin [3 1 2]
+    /// ex: argmin [4 1 5 1]
+    /// ex: argmin [3_2 1_5 1_4]
+    ///
+    /// See also: [argmax]
+    (1, ArgMin, MonadicArray, "argmin"),
+    /// Get the index of the maximum row of an array
+    ///
+    /// Rows are compared the same way as in [fall].
+    /// If there are several maximum rows, the index of the first one is returned.
+    /// ex: argmax [3 1 2]
+    /// ex: argmax [1 5 2 5]
+    /// ex: argmax "hello"
+    ///
+    /// See also: [argmin]
+    (1, ArgMax, MonadicArray, "argmax"),
+    /// Check if every element of an array is not `0`
+    ///
+    /// ex: all [1 1 1]
+    /// ex: all [1 0 1]
+    /// ex: all ≥0 [3_1 4_1 5_9]
+    /// [all] is like [reduce][minimum] on a [deshape]d mask, but it stops as soon as it finds a `0`.
+    /// ex: /↧ ♭ ≥0 [3_1 4_1 5_9]
+    /// The [all] of an empty array is `1`.
+    /// ex: all []
+    ///
+    /// See also: [any]
+    (1, All, MonadicArray, "all"),
+    /// Check if any element of an array is not `0`
+    ///
+    /// ex: any [0 0 1]
+    /// ex: any [0 0 0]
+    /// ex: any =5 [3_1 4_1 5_9]
+    /// [any] is like [reduce][maximum] on a [deshape]d mask, but it stops as soon as it finds a non-`0` element.
+    /// ex: /↥ ♭ =5 [3_1 4_1 5_9]
+    /// The [any] of an empty array is `0`.
+    /// ex: any []
+    ///
+    /// See also: [all]
+    (1, Any, MonadicArray, "any"),
     /// Get indices where array values are not equal to zero
     ///
     /// The most basic use is to convert a mask into a list of indices.
@@ -595,12 +883,34 @@ primitive!(
     /// ex: $ Count the characters is this string
     ///   : ⊕($"_ _"⊢∶⧻.) ⊛.⊏⍏.
     (1, Classify, MonadicArray, ("classify", '⊛')),
+    /// Get the indices of the rows in each class made by [classify]
+    ///
+    /// The result is a list of boxes, where box `k` holds the indices of the rows in class `k`.
+    /// ex: classmembers [9 1 9 1]
+    /// ex: classmembers "hello"
+    /// ex: classmembers [1_2 3_4 1_2]
+    (1, ClassMembers, MonadicArray, "classmembers"),
     /// Remove duplicate elements from an array
     ///
     /// ex: ⊝7_7_8_0_1_2_0
     /// ex: ⊝"Hello, World!"
     /// ex: ⊝[3_2 1_4 3_2 5_6 1_4 7_8]
     (1, Deduplicate, MonadicArray, ("deduplicate", '⊝')),
+    /// Remove duplicate rows from an array and get the index of each original row in the result
+    ///
+    /// The deduplicated array is returned along with a list of indices.
+    /// [select]ing from the deduplicated array with the indices reconstructs the original array.
+    /// ex: dedupinverse [3 1 3 2 1]
+    /// ex: ⊏dedupinverse "hello world"
+    /// ex: dedupinverse [1_2 3_4 1_2]
+    (1(2), DedupInverse, MonadicArray, "dedupinverse"),
+    /// Remove adjacent duplicate rows from an array
+    ///
+    /// Unlike [deduplicate], only runs of equal rows are collapsed, so a row may appear again later.
+    /// ex: dedupadjacent [1 1 2 1 1]
+    /// ex: dedupadjacent "Mississippi"
+    /// ex: dedupadjacent [1_2 1_2 3_4 1_2]
+    (1, DedupAdjacent, MonadicArray, "dedupadjacent"),
     /// Turn an array into a box
     ///
     /// This is Uiua's primary way to create nested or mixed-type arrays.
@@ -646,6 +956,16 @@ primitive!(
     /// ex: ≅ 1_2_3 [1 2 3]
     /// ex: ≅ 1_2_3 [1 2]
     (2, Match, DyadicArray, ("match", '≅')),
+    /// Check which elements of two arrays are the same
+    ///
+    /// If the arrays have the same [shape], the result is a mask that is `1` where the elements [match] and `0` where they differ.
+    /// ex: diff [1 2 3] [1 5 3]
+    /// ex: diff "hello" "help!"
+    /// ex: diff [1_2 3_4] [1_2 3_5]
+    /// ex: ⊚¬diff [1 2 3 4] [1 0 3 0]
+    /// If the shapes differ, the result is a list of the two [box]ed shapes instead.
+    /// ex: diff [1 2 3] [1 2]
+    (2, Diff, DyadicArray, "diff"),
     /// Combine two arrays as rows of a new array
     ///
     /// `first``shape` of the coupled array will *always* be `2`.
@@ -665,6 +985,16 @@ primitive!(
     /// [couple] is compatible with [under].
     /// ex: ⍜⊟'×2 3 5
     (2, Couple, DyadicArray, ("couple", '⊟')),
+    /// Stack a list of [box]ed arrays along a new first axis
+    ///
+    /// This is like [couple], but for any number of arrays.
+    /// ex: stack {1_2_3 4_5_6 7_8_9}
+    /// ex: stack {"abc" "def"}
+    /// By default, arrays with different shapes cannot be [stack]ed.
+    /// ex! stack {1_2_3 4_5}
+    /// Use [fill] to make their shapes match
+    /// ex: ⬚0stack {1_2_3 4_5 6}
+    (1, Stack, MonadicArray, "stack"),
     /// Split an array into two arrays
     (1(2), Uncouple, MonadicArray),
     /// Append two arrays end-to-end
@@ -690,6 +1020,18 @@ primitive!(
     ///
     /// [join]'s glyph is `⊂` because it kind of looks like a magnet pulling its two arguments together.
     (2, Join, DyadicArray, ("join", '⊂')),
+    /// [couple] two arrays if their [shape]s are the same, otherwise [join] them
+    ///
+    /// Arrays with the same [shape] are [couple]d into a new array with one more axis.
+    /// ex: joinorcouple [1 2] [3 4]
+    ///   : ⊂ [1 2] [3 4]
+    /// Arrays with different [shape]s are [join]ed as usual.
+    /// ex: joinorcouple [1_2 3_4] [5 6]
+    /// ex: joinorcouple [1 2] [3 4 5]
+    ///
+    /// This is useful for building up an array of rows one at a time, as the first two rows are [couple]d and the rest are [join]ed.
+    /// ex: joinorcouple ∶ 5_6 joinorcouple ∶ 3_4 1_2
+    (2, JoinOrCouple, DyadicArray, "joinorcouple"),
     /// Select multiple rows from an array
     ///
     /// For a scalar selector, [select] is equivalent to [pick].
@@ -701,6 +1043,11 @@ primitive!(
     /// If the selector's rank is `greater than``1`, then earch row of the selector will be selected separately.
     /// ex: ⊏ [0_1 1_2 2_3] [2 3 5 7]
     /// ex: ⊏ [0_1 1_2 2_0] [1_2_3 4_5_6 7_8_9]
+    ///
+    /// Indices work the same way as in [pick]. Negative indices count from the end.
+    /// ex: ⊏ ¯1_¯2 [1_2 3_4 5_6]
+    /// Out-of-bounds indices are an error unless a [fill] value is set.
+    /// ex: ⬚0⊏ 1_5 [8 3 9 2 0]
     (2, Select, DyadicArray, ("select", '⊏')),
     /// End step of under select
     (3, Unselect, Misc),
@@ -742,9 +1089,37 @@ primitive!(
     /// ex: ↯3_¯1_5 ⇡30
     /// If [fill] is used, the total number of elements in the new shape will always be [equal] to the total number of elements in the original shape.
     /// ex: ⬚0↯¯1_5 ⇡12
+    /// A negative dimension cannot be combined with a `0` dimension, as the derived length would be ambiguous.
+    /// ex! ↯¯1_0 ⇡6
+    ///
+    /// Shapes with a `0` dimension produce an empty array with that shape.
+    /// ex: △↯0_3 ⇡6
     ///
     /// See also: [deshape]
     (2, Reshape, DyadicArray, ("reshape", '↯')),
+    /// Change the shape of an array to match the shape of another array
+    ///
+    /// The first argument is the template, and the second is the array to reshape.
+    /// ex: reshapelike [1_2 3_4] [5 6 7 8]
+    /// The elements are cycled or truncated the same way as with [reshape].
+    /// ex: reshapelike [1_2_3 4_5_6] 1_2
+    /// ex: reshapelike ⇡5 [1_2 3_4]
+    /// [fill] works as well.
+    /// ex: ⬚0reshapelike [1_2_3 4_5_6] 1_2
+    ///
+    /// `reshapelike` is equivalent to `reshape``shape`.
+    /// ex: ↯△ [1_2 3_4] [5 6 7 8]
+    (2, ReshapeLike, DyadicArray, "reshapelike"),
+    /// Split the last axis of an array into two axes
+    ///
+    /// The first argument is the shape of the new trailing axes, and the second is the array to split.
+    /// ex: split 3_4 ↯2_12⇡24
+    /// The product of the new axes must be the length of the last axis.
+    /// ex! split 5_5 ↯2_12⇡24
+    ///
+    /// [invert]ing [split] [merge]s the axes back together.
+    /// ex: ⍘(split 3_4) ↯2_3_4⇡24
+    (2, Split, DyadicArray, "split"),
     /// Take the first n elements of an array
     ///
     /// This is the opposite of [drop].
@@ -787,7 +1162,23 @@ primitive!(
     ///
     /// Multi-dimensional rotations are supported.
     /// ex: ↻1_2 .↯4_5⇡20
+    ///
+    /// [under][rotate] rotates the array back by the same amount after the function is applied.
+    /// ex: ⍜(↻1)(⍜⊢(×10)) [1 2 3 4]
+    /// ex: ⍜(↻1_1)(⍜⊢(×10)) ↯3_3⇡9
     (2, Rotate, DyadicArray, ("rotate", '↻')),
+    /// Shift the elements of an array along an axis without wrapping around
+    ///
+    /// Positive amounts shift toward the end, and negative amounts shift toward the start.
+    /// Elements that are shifted off the end are dropped, and vacated positions are filled with the fill value.
+    /// ex: ⬚0shift 1 [1 2 3]
+    /// ex: ⬚0shift ¯2 [1 2 3 4 5]
+    /// ex: ⬚@-shift 2 "hello"
+    /// If no fill is set, numbers are filled with `0` and characters with spaces.
+    /// ex: shift 1 [1 2 3]
+    /// Multi-dimensional shifts are possible.
+    /// ex: ⬚0shift 1_¯1 .↯3_3⇡9
+    (2, Shift, DyadicArray, "shift"),
     /// The n-wise windows of an array
     ///
     /// ex: ◫2 .⇡4
@@ -824,6 +1215,10 @@ primitive!(
     /// ex: ⌕ 5 [1 8 5 2 3 5 4 5 6 7]
     /// ex: ⌕ "ab" "abracadabra"
     /// ex: ⌕ 1_2 . ↯4_4⇡3
+    ///
+    /// If a [fill] value is set, items of the searched-for array that are equal to it act as wildcards and match any item.
+    /// ex: ⬚0⌕ [1 0 3] [1 2 3 1 5 3]
+    /// ex: ⬚@?⌕ "c?t" "cat cot cut"
     (2, Find, DyadicArray, ("find", '⌕')),
     /// Check if each row of one array exists in another
     ///
@@ -876,6 +1271,18 @@ primitive!(
     /// [break]ing out of [reduce] discards the unreduced values.
     /// ex: /(⎋≥10.+) [3 4 8 9]
     (1[1], Reduce, AggregatingModifier, ("reduce", '/')),
+    /// Reduce along an axis of an array
+    ///
+    /// Takes a function, an axis, and an array.
+    /// [reduce] always reduces along the first axis. [reduceaxis] can reduce along any axis.
+    /// ex: reduceaxis+ 0 [1_2_3 4_5_6]
+    /// ex: reduceaxis+ 1 [1_2_3 4_5_6]
+    /// Negative axes count from the last axis.
+    /// ex: reduceaxis+ ¯1 [1_2_3 4_5_6]
+    /// ex: reduceaxis↥ ¯2 ↯2_3_4 ⇡24
+    /// For a matrix, reducing along the last axis is equivalent to [reduce] of the [transpose].
+    /// ex: /+⍉ [1_2_3 4_5_6]
+    (2[1], ReduceAxis, AggregatingModifier, "reduceaxis"),
     /// Apply a reducing function to an array with an initial value
     ///
     /// For reducing without an initial value, see [reduce].
@@ -891,16 +1298,42 @@ primitive!(
     /// [break]ing out of [fold] discards the unreduced values.
     /// ex: ∧(⎋≥10.+) 1 5_6_7_8
     (2[1], Fold, AggregatingModifier, ("fold", '∧')),
+    /// Fold an array with an initial value, collecting an output for each row
+    ///
+    /// The function must take 2 arguments and return 2 values.
+    /// Like [fold], the accumulator is the first argument. The new accumulator is the first value returned, and the output for the row is the second.
+    /// The final accumulator is returned above the array of outputs.
+    /// Here, a running sum is kept while each row's difference from the sum so far is output.
+    /// ex: mapaccum(⊃+-) 0 [1 4 9]
+    /// ex: mapaccum(⊃+(⊂∶)) 0 [1 2 3]
+    (2(2)[1], MapAccum, AggregatingModifier, "mapaccum"),
     /// Reduce, but keep intermediate values
     ///
     /// ex: \+   1_2_3_4
     /// ex: \-   1_2_3_4
     /// ex: \'-∶ 1_2_3_4
     ///
+    /// If the intermediate values have different shapes, they are [box]ed.
+    /// ex: \⊂ 1_2_3_4
+    /// [fill] can be used to make them uniform instead.
+    /// ex: ⬚0\⊂ 1_2_3_4
+    ///
     /// [break]ing out of [scan] appends the unscanned values without applying the function to them.
     /// This means the length of the output is always the same as that of the input.
     /// ex: \(⎋≥10.+) [1 2 3 4 5 6 7 8]
     (1[1], Scan, AggregatingModifier, ("scan", '\\')),
+    /// Reduce each overlapping window of an array
+    ///
+    /// Takes a function, a window size, and an array.
+    /// The function is used to [reduce] each window of that many rows, without making the [windows] array.
+    /// ex: windowreduce+ 2 [1 2 3 4]
+    /// ex: windowreduce↥ 3 [1 5 2 4 3 1]
+    /// ex: windowreduce+ 2 [1_2 3_4 5_6]
+    /// It is equivalent to [rows][reduce] on the [windows].
+    /// ex: ≡/+◫ 2 [1 2 3 4]
+    /// If the window size is greater than the [length] of the array, the result is empty.
+    /// ex: windowreduce+ 5 [1 2 3 4]
+    (2[1], WindowReduce, AggregatingModifier, "windowreduce"),
     /// Apply a function to each element of an array or arrays.
     ///
     /// This is the element-wise version of [rows].
@@ -955,6 +1388,11 @@ primitive!(
     /// ex: △⊞⊂ 1_2 3_4_5
     /// ex: △⊞+ [1_2_3 4_5_6] [7 8 9 10]
     /// ex: △⊞⊂ [1_2_3 4_5_6] [7 8 9 10]
+    ///
+    /// If the function's results do not all have the same [shape], they are [box]ed.
+    /// ex: ⊞(⇡+) 1_2 0_1
+    /// Use [fill] if you would rather pad the results.
+    /// ex: ⬚0⊞(⇡+) 1_2 0_1
     (2[1], Table, IteratingModifier, ("table", '⊞')),
     /// Apply a function to each combination of rows of two arrays
     ///
@@ -976,6 +1414,35 @@ primitive!(
     /// You can use [break] to break out of the loop.
     /// ex: ⍥(⎋>1000. ×2)∞ 1
     (1[1], Repeat, IteratingModifier, ("repeat", '⍥')),
+    /// Repeat a function a number of times, collecting every intermediate value
+    ///
+    /// Like [repeat], but the initial value and the result of each repetition are all kept.
+    /// The function must take 1 argument and return 1 value.
+    /// The result has [length] one more than the number of repetitions.
+    /// ex: iterate(×2)3 1
+    /// ex: iterate(+1_1)4 [0 0]
+    /// You can use [break] to stop early.
+    /// ex: iterate(⎋>100. ×3)10 1
+    (2[1], Iterate, IteratingModifier, "iterate"),
+    /// Call a function a number of times, collecting the value it returns on top into an array
+    ///
+    /// The function must return 1 more value than it takes.
+    /// The values below the top are passed to the next call, and the last ones are kept below the collected array.
+    /// ex: collect(.×2)5 1
+    /// ex: collect(⇡3)4
+    /// ex: collect(⊃∘(+1))4 0
+    /// This is much faster than [join]ing rows one at a time.
+    (1[1], Collect, IteratingModifier, "collect"),
+    /// Repeat a function while a condition holds
+    ///
+    /// The first function is the loop body, and the second is the condition.
+    /// The condition must return a boolean. It is called on copies of its arguments, so it does not consume them.
+    /// The body must return as many values as it takes.
+    /// ex: do(-1)(>0) 5
+    /// ex: do(×2)(<1000) 1
+    /// You can also use [break] to stop early.
+    /// ex: do(⎋>5.+1)(1) 0
+    ([2], Do, IteratingModifier, "do"),
     /// Group elements of an array into buckets by index
     ///
     /// Takes a function and two arrays.
@@ -1000,8 +1467,29 @@ primitive!(
     /// ex: $ Count the characters is this string
     ///   : ⊕{⊢∶⧻.} ⊛.⊏⍏.
     ///
+    /// [under][group][box] lets you modify each group and put its rows back where they came from.
+    /// ex: ⍜(⊕□)(∵⍜⊔⇌) [0 1 0 2 1 1] [1 2 3 4 5 6]
+    ///
     /// [group] is closely related to [partition].
     (2[1], Group, AggregatingModifier, ("group", '⊕')),
+    /// Inverse of group
+    (3, Ungroup, Misc),
+    /// Group elements of an array into buckets by index and reduce each bucket
+    ///
+    /// Takes a function and two arrays, like [group].
+    /// The function must take 2 arguments. Each group is reduced with it as in [reduce].
+    /// ex: groupreduce+ [0 1 0 1] [10 20 30 40]
+    /// ex: groupreduce↥ [0 2 2 1 0 1] [1 2 3 4 5 6]
+    /// This is like [group][reduce], but the groups are never made into separate arrays, so it is much faster.
+    /// ex: ⊕/+ [0 1 0 1] [10 20 30 40]
+    ///
+    /// Like [group][reduce], a group with no rows is only allowed when the function is exactly [add], [multiply], [maximum], or [minimum] and the array is numeric. The group becomes that function's identity.
+    /// ex: groupreduce+ [0 2] [1 2]
+    /// ex: ⊕/+ [0 2] [1 2]
+    /// With any other function, even one that wraps those, an empty group is an error.
+    /// ex! groupreduce(∘+) [0 2] [1 2]
+    /// ex! groupreduce- [0 2] [1 2]
+    (2[1], GroupReduce, AggregatingModifier, "groupreduce"),
     /// Group elements of an array into buckets by sequential keys
     ///
     /// Takes a function and two arrays.
@@ -1025,8 +1513,38 @@ primitive!(
     /// This can be used to split an array by a delimiter.
     /// ex: ⊜□ ≠@ . $ Hey there friendo
     ///
+    /// [under][partition][box] lets you modify each group and put its rows back where they came from.
+    /// ex: ⍜(⊜□)(∵⍜⊔⇌) ≠@ . $ Hey there friendo
+    ///
     /// [partition] is closely related to [group].
     (2[1], Partition, AggregatingModifier, ("partition", '⊜')),
+    /// Inverse of partition
+    (3, Unpartition, Misc),
+    /// Classify the rows of an array by a key function
+    ///
+    /// The function is called on each row to get its key.
+    /// Rows with [match]ing keys get the same class, like with [classify].
+    /// ex: classifyby(◿3) [1 2 3 4 5 6]
+    /// Keys are compared as whole arrays, so a key function that returns a list classifies by all of its elements jointly.
+    /// ex: classifyby(↙2) [1_2_3 1_2_4 2_1_3 1_2_5]
+    ///
+    /// See also: [groupby]
+    (1[1], ClassifyBy, AggregatingModifier, "classifyby"),
+    /// Group the rows of an array by a key function
+    ///
+    /// Takes two functions and an array.
+    /// The first function is called on each row to get its key.
+    /// Rows with [match]ing keys are grouped together in order of their first appearance.
+    /// The second function then processes each group.
+    /// ex: groupby(◿3)□ [1 2 3 4 5 6]
+    /// Keys are compared as whole arrays, so a key function that returns a list groups by all of its elements jointly.
+    /// ex: groupby(↙2)⧻ [1_2_3 1_2_4 2_1_3 1_2_5]
+    ///
+    /// `groupby``f``g` is equivalent to [group]`g`[classifyby]`f`[duplicate].
+    /// ex: ⊕□classifyby(◿3). [1 2 3 4 5 6]
+    ///
+    /// See also: [classifyby]
+    (1[2], GroupBy, AggregatingModifier, "groupby"),
     /// Call a function on two sets of values
     ///
     /// For monadic functions, [both] calls its function on each of the top 2 values on the stack.
@@ -1261,6 +1779,9 @@ primitive!(
     /// [fill][reshape] fills in the shape with the fill element instead of cycling the data.
     /// ex:   ↯3_5 ⇡9
     /// ex: ⬚0↯3_5 ⇡9
+    ///
+    /// When [fill] is used in the first function of [under], the fill is only active for the first step, not for the second function or the undoing step.
+    /// ex: ⍜(⬚0↙5)(×10) [1 2 3]
     ([2], Fill, OtherModifier, ("fill", '⬚')),
     /// Compose two functions
     ///
@@ -1421,10 +1942,22 @@ primitive!(
     /// ex: parse "17"
     /// ex: parse "3.1415926535897932"
     /// ex! parse "dog"
+    /// If the string has several whitespace-separated parts, the error says which one is not a number.
+    /// ex! parse "1 2 x 4"
     (1, Parse, Misc, "parse"),
+    /// Get the approximate number of bytes used by an array's elements
+    ///
+    /// This is useful for understanding the memory cost of your data.
+    /// ex: memsize ⇡10
+    /// Arrays of booleans are stored more compactly than other numbers.
+    /// ex: memsize =1 ⇡10
+    /// [box]ed arrays include the sizes of their contents.
+    /// ex: memsize {⇡10 "hello"}
+    (1, MemSize, Misc, "memsize"),
     /// Generate a random number between 0 and 1
     ///
     /// If you need a seeded random number, use [gen].
+    /// To make the numbers the same every time the program runs, seed the generator with [&seed].
     ///
     /// ex: ⚂
     /// ex: [⚂⚂⚂]
@@ -1432,6 +1965,15 @@ primitive!(
     /// Use [multiply] and [floor] to generate a random integer in a range.
     /// ex: ⌊×10 [⍥⚂5]
     (0, Rand, Misc, ("random", '⚂')),
+    /// Generate an array of random numbers between 0 and 1
+    ///
+    /// The argument is the shape of the array.
+    /// ex: randarray 5
+    /// ex: randarray 2_3
+    ///
+    /// This is much faster than generating each number with [random].
+    /// ex: ⌊×10 randarray 10
+    (1, RandArray, Misc, "randarray"),
     /// Generate a random number between 0 and 1 from a seed, as well as the next seed
     ///
     /// If you don't care about a seed, you can use [random].
@@ -1472,9 +2014,11 @@ primitive!(
     /// `0` indicates a number array.
     /// `1` indicates a character array.
     /// `2` indicates a function array.
+    /// `3` indicates a complex array.
     /// ex: type 5
     /// ex: type "hello"
     /// ex: type (+)
+    /// ex: type complex 1 2
     /// ex: ∵type  {10 "dog" (≅⇌.)}
     ///   : ∵(|1 type!) {10 "dog" (≅⇌.)}
     (1, Type, Misc, "type"),
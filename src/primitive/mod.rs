@@ -8,6 +8,7 @@ pub use defs::*;
 use std::{
     borrow::Cow,
     cell::RefCell,
+    collections::HashMap,
     f64::{
         consts::{PI, TAU},
         INFINITY,
@@ -27,7 +28,6 @@ use crate::{
     algorithm::{fork, loops},
     array::Array,
     function::Function,
-    grid_fmt::GridFmt,
     lex::AsciiToken,
     run::FunctionArg,
     sys::*,
@@ -115,10 +115,14 @@ impl fmt::Display for Primitive {
             match self {
                 InvTranspose => write!(f, "⍘{Transpose}"),
                 InverseBits => write!(f, "⍘{Bits}"),
+                InverseBitsMsb => write!(f, "⍘{BitsMsb}"),
                 InvTrace => write!(f, "⍘{Trace}"),
                 InvWhere => write!(f, "⍘{Where}"),
                 Uncouple => write!(f, "⍘{Couple}"),
                 Untake => write!(f, "⍘{Take}"),
+                Undeshape => write!(f, "⍘{Deshape}"),
+                Ungroup => write!(f, "⍘{Group}"),
+                Unpartition => write!(f, "⍘{Partition}"),
                 Undrop => write!(f, "⍘{Drop}"),
                 Unselect => write!(f, "⍘{Select}"),
                 Unpick => write!(f, "⍘{Pick}"),
@@ -189,6 +193,8 @@ impl Primitive {
             InvTranspose => Transpose,
             Bits => InverseBits,
             InverseBits => Bits,
+            BitsMsb => InverseBitsMsb,
+            InverseBitsMsb => BitsMsb,
             Couple => Uncouple,
             Roll => Unroll,
             Unroll => Roll,
@@ -232,23 +238,56 @@ impl Primitive {
         let exact_match = res.names().unwrap().text == name;
         (exact_match || matching.next().is_none()).then_some(res)
     }
+    /// Try to parse a primitive from a name prefix, checking custom aliases first
+    pub fn from_format_name_with_aliases(
+        name: &str,
+        aliases: &HashMap<String, Primitive>,
+    ) -> Option<Self> {
+        (aliases.get(name).copied()).or_else(|| Primitive::from_format_name(name))
+    }
     /// Try to parse multiple primitives from the concatenation of their name prefixes
+    ///
+    /// The name is split greedily, taking the longest name at each point.
+    /// If that does not parse the whole name, the split with the fewest primitives is used instead.
     pub fn from_format_name_multi(name: &str) -> Option<Vec<(Self, &str)>> {
+        Primitive::from_format_name_multi_with_aliases(name, &HashMap::new())
+    }
+    /// Try to parse multiple primitives from the concatenation of their name prefixes,
+    /// checking custom aliases first
+    ///
+    /// Unlike the built-in names, aliases may be a single character.
+    pub fn from_format_name_multi_with_aliases<'a>(
+        name: &'a str,
+        aliases: &HashMap<String, Primitive>,
+    ) -> Option<Vec<(Self, &'a str)>> {
+        if let Some(&prim) = aliases.get(name) {
+            return Some(vec![(prim, name)]);
+        }
         let indices: Vec<usize> = name.char_indices().map(|(i, _)| i).collect();
         if indices.len() < 2 {
             return None;
         }
+        let sub_name = |start: usize, end: usize| {
+            &name[indices[start]..indices.get(end).copied().unwrap_or(name.len())]
+        };
+        Primitive::greedy_split(indices.len(), sub_name, aliases)
+            .or_else(|| Primitive::fewest_split(indices.len(), sub_name, aliases))
+    }
+    /// Split a name by taking the longest name at each point
+    fn greedy_split<'a>(
+        char_count: usize,
+        sub_name: impl Fn(usize, usize) -> &'a str,
+        aliases: &HashMap<String, Primitive>,
+    ) -> Option<Vec<(Self, &'a str)>> {
         let mut prims = Vec::new();
         let mut start = 0;
         'outer: loop {
-            if start == indices.len() {
+            if start == char_count {
                 break Some(prims);
             }
-            for len in (2..=indices.len() - start).rev() {
-                let start_index = indices[start];
-                let end_index = indices.get(start + len).copied().unwrap_or(name.len());
-                let sub_name = &name[start_index..end_index];
-                if let Some(p) = Primitive::from_format_name(sub_name) {
+            for len in (1..=char_count - start).rev() {
+                let sub_name = sub_name(start, start + len);
+                if let Some(p) = Primitive::from_format_name_with_aliases(sub_name, aliases) {
                     prims.push((p, sub_name));
                     start += len;
                     continue 'outer;
@@ -257,6 +296,83 @@ impl Primitive {
             break None;
         }
     }
+    /// Split a name into the fewest primitives
+    ///
+    /// Ties are broken by preferring longer names earlier in the string.
+    fn fewest_split<'a>(
+        char_count: usize,
+        sub_name: impl Fn(usize, usize) -> &'a str,
+        aliases: &HashMap<String, Primitive>,
+    ) -> Option<Vec<(Self, &'a str)>> {
+        // For each character, the fewest primitives that parse the rest of the name,
+        // and the length and primitive of the first of them
+        let mut counts: Vec<Option<usize>> = vec![None; char_count + 1];
+        let mut firsts: Vec<Option<(usize, Primitive)>> = vec![None; char_count];
+        counts[char_count] = Some(0);
+        for start in (0..char_count).rev() {
+            for len in (1..=char_count - start).rev() {
+                let Some(rest) = counts[start + len] else {
+                    continue;
+                };
+                if counts[start].is_some_and(|count| count <= rest + 1) {
+                    continue;
+                }
+                let sub_name = sub_name(start, start + len);
+                if let Some(p) = Primitive::from_format_name_with_aliases(sub_name, aliases) {
+                    counts[start] = Some(rest + 1);
+                    firsts[start] = Some((len, p));
+                }
+            }
+        }
+        let mut prims = Vec::new();
+        let mut start = 0;
+        while start < char_count {
+            let (len, p) = firsts[start]?;
+            prims.push((p, sub_name(start, start + len)));
+            start += len;
+        }
+        Some(prims)
+    }
+    /// Get every way to parse multiple primitives from the concatenation of their name prefixes
+    ///
+    /// Splits that use longer names earlier in the string come first.
+    pub fn from_format_name_multi_all(name: &str) -> Vec<Vec<(Self, &str)>> {
+        Primitive::from_format_name_multi_all_with_aliases(name, &HashMap::new())
+    }
+    /// Get every way to parse multiple primitives from the concatenation of their name prefixes,
+    /// checking custom aliases first
+    pub fn from_format_name_multi_all_with_aliases<'a>(
+        name: &'a str,
+        aliases: &HashMap<String, Primitive>,
+    ) -> Vec<Vec<(Self, &'a str)>> {
+        fn splits<'a>(
+            name: &'a str,
+            aliases: &HashMap<String, Primitive>,
+            prefix: &mut Vec<(Primitive, &'a str)>,
+            all: &mut Vec<Vec<(Primitive, &'a str)>>,
+        ) {
+            if name.is_empty() {
+                all.push(prefix.clone());
+                return;
+            }
+            let ends: Vec<usize> = (name.char_indices().map(|(i, _)| i).skip(1))
+                .chain([name.len()])
+                .collect();
+            for end in ends.into_iter().rev() {
+                let sub_name = &name[..end];
+                if let Some(p) = Primitive::from_format_name_with_aliases(sub_name, aliases) {
+                    prefix.push((p, sub_name));
+                    splits(&name[end..], aliases, prefix, all);
+                    prefix.pop();
+                }
+            }
+        }
+        let mut all = Vec::new();
+        if name.chars().count() >= 2 || aliases.contains_key(name) {
+            splits(name, aliases, &mut Vec::new(), &mut all);
+        }
+        all
+    }
     pub fn as_constant(&self) -> Option<f64> {
         Some(match self {
             Primitive::Pi => PI,
@@ -287,9 +403,15 @@ impl Primitive {
             Primitive::Cos => env.monadic_env(Value::cos)?,
             Primitive::Asin => env.monadic_env(Value::asin)?,
             Primitive::Acos => env.monadic_env(Value::acos)?,
+            Primitive::SinDeg => env.monadic_env(Value::sin_deg)?,
+            Primitive::CosDeg => env.monadic_env(Value::cos_deg)?,
             Primitive::Floor => env.monadic_env(Value::floor)?,
             Primitive::Ceil => env.monadic_env(Value::ceil)?,
+            Primitive::Trunc => env.monadic_env(Value::trunc)?,
+            Primitive::RoundAway => env.monadic_env(Value::round_away)?,
             Primitive::Round => env.monadic_env(Value::round)?,
+            Primitive::IsNaN => env.monadic_env(Value::is_nan)?,
+            Primitive::IsInfinite => env.monadic_env(Value::is_infinite)?,
             Primitive::Eq => env.dyadic_rr_env(Value::is_eq)?,
             Primitive::Ne => env.dyadic_rr_env(Value::is_ne)?,
             Primitive::Lt => env.dyadic_rr_env(Value::is_lt)?,
@@ -306,10 +428,21 @@ impl Primitive {
             Primitive::Min => env.dyadic_rr_env(Value::min)?,
             Primitive::Max => env.dyadic_rr_env(Value::max)?,
             Primitive::Atan => env.dyadic_rr_env(Value::atan2)?,
+            Primitive::AtanDeg => env.dyadic_rr_env(Value::atan2_deg)?,
+            Primitive::RoundTo => env.dyadic_rr_env(Value::round_to)?,
+            Primitive::AddSat => env.dyadic_rr_env(Value::add_sat)?,
+            Primitive::SubSat => env.dyadic_rr_env(Value::sub_sat)?,
+            Primitive::MulSat => env.dyadic_rr_env(Value::mul_sat)?,
+            Primitive::Gcd => env.dyadic_rr_env(Value::gcd)?,
+            Primitive::Lcm => env.dyadic_rr_env(Value::lcm)?,
+            Primitive::Complex => env.dyadic_rr_env(Value::complex)?,
             Primitive::Match => env.dyadic_rr(|a, b| a == b)?,
+            Primitive::Diff => env.dyadic_rr(Value::diff)?,
             Primitive::Join => env.dyadic_oo_env(Value::join)?,
+            Primitive::JoinOrCouple => env.dyadic_oo_env(Value::join_or_couple)?,
             Primitive::Transpose => env.monadic_mut(Value::transpose)?,
             Primitive::InvTranspose => env.monadic_mut(Value::inv_transpose)?,
+            Primitive::BoxTranspose => env.monadic_env(Value::box_transpose)?,
             Primitive::Keep => env.dyadic_ro_env(Value::keep)?,
             Primitive::Unkeep => {
                 let from = env.pop(1)?;
@@ -318,6 +451,22 @@ impl Primitive {
                 env.push(from.unkeep(counts, into, env)?);
             }
             Primitive::Take => env.dyadic_oo_env(Value::take)?,
+            Primitive::Ungroup => {
+                let from = env.pop(1)?;
+                let indices = env.pop(2)?;
+                let indices =
+                    indices.as_indices(env, "Group indices must be a list of integers")?;
+                let into = env.pop(3)?;
+                env.push(from.ungroup(&indices, into, env)?);
+            }
+            Primitive::Unpartition => {
+                let from = env.pop(1)?;
+                let markers = env.pop(2)?;
+                let markers =
+                    markers.as_indices(env, "Partition indices must be a list of integers")?;
+                let into = env.pop(3)?;
+                env.push(from.unpartition(&markers, into, env)?);
+            }
             Primitive::Untake => {
                 let from = env.pop(1)?;
                 let index = env.pop(2)?;
@@ -332,7 +481,32 @@ impl Primitive {
                 env.push(from.undrop(index, into, env)?);
             }
             Primitive::Rotate => env.dyadic_ro_env(Value::rotate)?,
+            Primitive::Shift => env.dyadic_ro_env(Value::shift)?,
             Primitive::Couple => env.dyadic_oo_env(Value::couple)?,
+            Primitive::Stack => {
+                let boxes = env.pop(1)?;
+                let Value::Func(boxes) = boxes else {
+                    return Err(env.error(format!(
+                        "Argument to stack must be a list of boxed arrays, but it is {}",
+                        boxes.type_name()
+                    )));
+                };
+                if boxes.rank() != 1 {
+                    return Err(env.error(format!(
+                        "Argument to stack must be a list of boxed arrays, but its rank is {}",
+                        boxes.rank()
+                    )));
+                }
+                let arrays = boxes
+                    .data
+                    .iter()
+                    .map(|f| match f.as_constant() {
+                        Some(value) => value.clone(),
+                        None => Function::clone(f).into(),
+                    })
+                    .collect();
+                env.push(Value::stack(arrays, env)?);
+            }
             Primitive::Uncouple => {
                 let coupled = env.pop(1)?;
                 let (a, b) = coupled.uncouple(env)?;
@@ -341,6 +515,13 @@ impl Primitive {
             }
             Primitive::Rise => env.monadic_ref_env(|v, env| v.rise(env))?,
             Primitive::Fall => env.monadic_ref_env(|v, env| v.fall(env))?,
+            Primitive::Sort => env.monadic_mut(Value::sort_up)?,
+            Primitive::SortDown => env.monadic_mut(Value::sort_down)?,
+            Primitive::ToGrid => env.monadic_env(Value::to_grid)?,
+            Primitive::ArgMin => env.monadic_ref_env(|v, env| v.argmin(env).map(|i| i as f64))?,
+            Primitive::ArgMax => env.monadic_ref_env(|v, env| v.argmax(env).map(|i| i as f64))?,
+            Primitive::All => env.monadic_ref_env(Value::all)?,
+            Primitive::Any => env.monadic_ref_env(Value::any)?,
             Primitive::Pick => env.dyadic_oo_env(Value::pick)?,
             Primitive::Unpick => {
                 let from = env.pop(1)?;
@@ -359,7 +540,15 @@ impl Primitive {
             Primitive::Where => env.monadic_ref_env(Value::wher)?,
             Primitive::InvWhere => env.monadic_ref_env(Value::inverse_where)?,
             Primitive::Classify => env.monadic_ref_env(Value::classify)?,
+            Primitive::ClassMembers => env.monadic_ref_env(Value::class_members)?,
             Primitive::Deduplicate => env.monadic_mut(Value::deduplicate)?,
+            Primitive::DedupInverse => {
+                let mut array = env.pop(1)?;
+                let inverse = array.dedup_inverse();
+                env.push(array);
+                env.push(inverse.into_iter().collect::<Value>());
+            }
+            Primitive::DedupAdjacent => env.monadic_mut(Value::dedup_adjacent)?,
             Primitive::Member => env.dyadic_rr_env(Value::member)?,
             Primitive::Find => env.dyadic_rr_env(Value::find)?,
             Primitive::IndexOf => env.dyadic_rr_env(Value::index_of)?,
@@ -382,8 +571,16 @@ impl Primitive {
             Primitive::Parse => env.monadic_env(|v, env| v.parse_num(env))?,
             Primitive::Range => env.monadic_ref_env(Value::range)?,
             Primitive::Reverse => env.monadic_mut(Value::reverse)?,
-            Primitive::Deshape => env.monadic_mut(Value::deshape)?,
+            Primitive::Deshape => env.monadic_env(Value::deshape_fill)?,
+            Primitive::Undeshape => {
+                let shape = env.pop(1)?;
+                let mut val = env.pop(2)?;
+                val.undeshape(&shape, env)?;
+                env.push(val);
+            }
+            Primitive::Merge => env.monadic_env(Value::merge)?,
             Primitive::First => env.monadic_env(Value::first)?,
+            Primitive::Head => env.monadic_env(Value::head)?,
             Primitive::Last => env.monadic_env(Value::last)?,
             Primitive::Len => env.monadic_ref(Value::row_count)?,
             Primitive::Shape => {
@@ -391,8 +588,12 @@ impl Primitive {
             }
             Primitive::Bits => env.monadic_ref_env(Value::bits)?,
             Primitive::InverseBits => env.monadic_ref_env(Value::inverse_bits)?,
+            Primitive::BitsMsb => env.monadic_ref_env(Value::bits_msb)?,
+            Primitive::InverseBitsMsb => env.monadic_ref_env(Value::inverse_bits_msb)?,
             Primitive::Fold => loops::fold(env)?,
+            Primitive::MapAccum => loops::map_accum(env)?,
             Primitive::Reduce => loops::reduce(env)?,
+            Primitive::ReduceAxis => loops::reduce_axis(env)?,
             Primitive::Each => loops::each(env)?,
             Primitive::Rows => loops::rows(env)?,
             Primitive::Distribute => loops::distribute(env)?,
@@ -400,15 +601,35 @@ impl Primitive {
             Primitive::Cross => loops::cross(env)?,
             Primitive::Scan => loops::scan(env)?,
             Primitive::Repeat => loops::repeat(env)?,
+            Primitive::Iterate => loops::iterate(env)?,
+            Primitive::Collect => loops::collect(env)?,
+            Primitive::Do => loops::do_while(env)?,
             Primitive::Level => loops::level(env)?,
             Primitive::Group => loops::group(env)?,
+            Primitive::GroupReduce => loops::group_reduce(env)?,
             Primitive::Partition => loops::partition(env)?,
+            Primitive::WindowReduce => loops::window_reduce(env)?,
+            Primitive::ClassifyBy => loops::classify_by(env)?,
+            Primitive::GroupBy => loops::group_by(env)?,
             Primitive::Reshape => {
                 let shape = env.pop(1)?;
                 let mut array = env.pop(2)?;
                 array.reshape(&shape, env)?;
                 env.push(array);
             }
+            Primitive::Split => {
+                let trailing = env.pop(1)?;
+                let mut array = env.pop(2)?;
+                array.split(&trailing, env)?;
+                env.push(array);
+            }
+            Primitive::ReshapeLike => {
+                let template = env.pop(1)?;
+                let mut array = env.pop(2)?;
+                let shape: Value = template.shape().iter().copied().collect();
+                array.reshape(&shape, env)?;
+                env.push(array);
+            }
             Primitive::Break => {
                 let n = env.pop(1)?.as_nat(env, "Break expects a natural number")?;
                 if n > 0 {
@@ -456,6 +677,29 @@ impl Primitive {
                 env.push(a);
                 env.push(c);
             }
+            Primitive::DupN => {
+                let depth = env.pop(1)?.as_nat(env, "Depth must be a natural number")?;
+                let mut values = Vec::with_capacity(depth + 1);
+                for i in 0..=depth {
+                    values.push(env.pop(i + 2)?);
+                }
+                let x = values[depth].clone();
+                for value in values.into_iter().rev() {
+                    env.push(value);
+                }
+                env.push(x);
+            }
+            Primitive::DropN => {
+                let depth = env.pop(1)?.as_nat(env, "Depth must be a natural number")?;
+                let mut values = Vec::with_capacity(depth);
+                for i in 0..depth {
+                    values.push(env.pop(i + 2)?);
+                }
+                env.pop(depth + 2)?;
+                for value in values.into_iter().rev() {
+                    env.push(value);
+                }
+            }
             Primitive::Dip => {
                 let f = env.pop(FunctionArg(1))?;
                 let x = env.pop(1)?;
@@ -522,11 +766,20 @@ impl Primitive {
                     return Err(UiuaError::Throw(msg.into(), env.span().clone()));
                 }
             }
-            Primitive::Rand => {
-                thread_local! {
-                    static RNG: RefCell<SmallRng> = RefCell::new(SmallRng::seed_from_u64(instant::now().to_bits()));
-                }
-                env.push(RNG.with(|rng| rng.borrow_mut().gen::<f64>()));
+            Primitive::Rand => env.push(RNG.with(|rng| rng.borrow_mut().gen::<f64>())),
+            Primitive::MemSize => env.monadic_ref(|v| v.mem_size() as f64)?,
+            Primitive::RandArray => {
+                let shape = env.pop(1)?.as_naturals(
+                    env,
+                    "Shape must be a natural number \
+                    or list of natural numbers",
+                )?;
+                let len: usize = shape.iter().product();
+                let data: Vec<f64> = RNG.with(|rng| {
+                    let mut rng = rng.borrow_mut();
+                    (0..len).map(|_| rng.gen()).collect()
+                });
+                env.push(Array::new(shape.as_slice(), data));
             }
             Primitive::Gen => {
                 let seed = env.pop(1)?;
@@ -564,6 +817,7 @@ impl Primitive {
                     Value::Num(_) | Value::Byte(_) => 0,
                     Value::Char(_) => 1,
                     Value::Func(_) => 2,
+                    Value::Complex(_) => 3,
                 });
             }
             Primitive::Sig => {
@@ -591,6 +845,16 @@ impl Primitive {
     }
 }
 
+thread_local! {
+    /// The random number generator used by [`Primitive::Rand`] and [`Primitive::RandArray`]
+    static RNG: RefCell<SmallRng> = RefCell::new(SmallRng::seed_from_u64(instant::now().to_bits()));
+}
+
+/// Reseed the current thread's random number generator
+pub fn seed_random(seed: u64) {
+    RNG.with(|rng| *rng.borrow_mut() = SmallRng::seed_from_u64(seed));
+}
+
 fn trace(env: &mut Uiua, inverse: bool) -> UiuaResult {
     let val = env.pop(1)?;
     let span: String = if inverse {
@@ -600,7 +864,10 @@ fn trace(env: &mut Uiua, inverse: bool) -> UiuaResult {
     };
     let max_line_len = span.chars().count() + 2;
     let item_lines = format_trace_item_lines(
-        val.grid_string().lines().map(Into::into).collect(),
+        val.show_with_precision(env.display_precision())
+            .lines()
+            .map(Into::into)
+            .collect(),
         max_line_len,
     );
     env.push(val);
@@ -635,9 +902,10 @@ fn dump(env: &mut Uiua) -> UiuaResult {
         }
     }
     let max_line_len = span.chars().count() + 2;
+    let precision = env.display_precision();
     let item_lines: Vec<Vec<String>> = items
         .iter()
-        .map(Value::grid_string)
+        .map(|item| item.show_with_precision(precision))
         .map(|s| s.lines().map(Into::into).collect::<Vec<String>>())
         .map(|lines| format_trace_item_lines(lines, max_line_len))
         .collect();
@@ -796,9 +1064,26 @@ impl PrimExample {
         self.should_error
     }
     pub fn should_run(&self) -> bool {
-        !["&sl", "&tcpc"]
-            .iter()
-            .any(|prim| self.input.contains(prim))
+        ![
+            "&sl",
+            "&tcpc",
+            "&env",
+            "&getkey",
+            "&imgwrite",
+            "&imgread",
+            "&cputime",
+            "&timer",
+            "&dt",
+            "&flush",
+            "&fread",
+            "&fappend",
+            "&fwatch",
+            "&csrand",
+            "&sysmem",
+            "&exit",
+        ]
+        .iter()
+        .any(|prim| self.input.contains(prim))
     }
     pub fn output(&self) -> &Result<Vec<String>, String> {
         self.output.get_or_init(|| {
@@ -960,6 +1245,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn seed_repeats_rand() {
+        let mut env = Uiua::with_native_sys();
+        let mut run = |seed: u8| {
+            env.load_str(&format!("[⍥⚂10] &seed {seed}")).unwrap();
+            env.pop("numbers").unwrap()
+        };
+        let a = run(1);
+        assert_eq!(a.shape(), [10]);
+        assert_eq!(a, run(1));
+        assert_ne!(a, run(2));
+    }
+
+    #[test]
+    fn rand_array() {
+        let run = || {
+            seed_random(0);
+            let mut env = Uiua::with_native_sys();
+            env.load_str("randarray 2_3").unwrap();
+            env.pop(1).unwrap()
+        };
+        let a = run();
+        assert_eq!(a.shape(), [2, 3]);
+        assert_eq!(a, run());
+        let Value::Num(nums) = &a else {
+            panic!("randarray should produce numbers");
+        };
+        assert!(nums.data.iter().all(|n| (0.0..1.0).contains(n)));
+    }
+
     #[test]
     fn prim_docs() {
         for prim in Primitive::all() {
@@ -1015,6 +1330,37 @@ mod tests {
         assert_eq!(Primitive::from_format_name_multi("foo"), None);
     }
 
+    #[test]
+    fn from_multiname_ambiguous() {
+        // Both "where call" and "whe rec all" are valid
+        let all = Primitive::from_format_name_multi_all("wherecall");
+        let names: Vec<Vec<&str>> = (all.iter())
+            .map(|split| split.iter().map(|(_, name)| *name).collect())
+            .collect();
+        assert_eq!(names, [vec!["where", "call"], vec!["whe", "rec", "all"]]);
+        assert_eq!(
+            Primitive::from_format_name_multi("wherecall"),
+            Some(all[0].clone())
+        );
+        assert!(Primitive::from_format_name_multi_all("foo").is_empty());
+        // Greedy splitting is used when it parses the whole name
+        let names = |name| {
+            (Primitive::from_format_name_multi(name).unwrap().into_iter())
+                .map(|(_, name)| name)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names("duplicatededu"), ["duplicate", "dedu"]);
+        // Otherwise, the split with the fewest primitives is used
+        assert_eq!(names("flipop"), ["fli", "pop"]);
+        // Aliases are considered for every split
+        let aliases = [("sq".to_string(), Primitive::Sqrt)].into();
+        let all = Primitive::from_format_name_multi_all_with_aliases("sqrev", &aliases);
+        assert_eq!(
+            all,
+            [vec![(Primitive::Sqrt, "sq"), (Primitive::Reverse, "rev")]]
+        );
+    }
+
     #[cfg(test)]
     #[test]
     fn gen_grammar_file() {
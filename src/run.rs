@@ -6,7 +6,10 @@ use std::{
     panic::{catch_unwind, AssertUnwindSafe},
     path::{Path, PathBuf},
     str::FromStr,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
 };
 
 use instant::Duration;
@@ -14,11 +17,12 @@ use parking_lot::Mutex;
 
 use crate::{
     array::Array,
+    complex::Complex,
     function::*,
     lex::Span,
-    parse::parse,
+    parse::parse_with_aliases,
     primitive::{Primitive, CONSTANTS},
-    value::Value,
+    value::{FromValue, Value},
     Diagnostic, DiagnosticKind, Handle, Ident, NativeSys, SysBackend, TraceFrame, UiuaError,
     UiuaResult,
 };
@@ -48,6 +52,26 @@ pub struct Uiua {
     execution_limit: Option<f64>,
     /// The time at which execution started
     execution_start: f64,
+    /// The maximum depth of nested function calls
+    recursion_limit: usize,
+    /// The current depth of nested function calls
+    call_depth: usize,
+    /// A limit on the number of execution steps
+    step_budget: Option<usize>,
+    /// The number of execution steps taken so far
+    steps: usize,
+    /// Whether the step budget has already been exceeded and started over
+    step_budget_extended: bool,
+    /// A limit on the number of elements made by range
+    pub(crate) range_limit: Option<usize>,
+    /// The maximum number of significant digits to show when pretty printing numbers
+    display_precision: Option<usize>,
+    /// Set from another thread to stop execution
+    interrupt: Arc<AtomicBool>,
+    /// Custom names for primitives, checked before the built-in names
+    primitive_aliases: HashMap<String, Primitive>,
+    /// Whether each may run pure functions on large arrays in parallel
+    parallel_each: bool,
     /// The paths of files currently being imported (used to detect import cycles)
     current_imports: Arc<Mutex<HashSet<PathBuf>>>,
     /// The stacks of imported files
@@ -102,6 +126,7 @@ impl Default for Scope {
 #[derive(Default, Clone)]
 struct Fills {
     nums: Vec<f64>,
+    complexes: Vec<Complex>,
     chars: Vec<char>,
     functions: Vec<Arc<Function>>,
 }
@@ -118,6 +143,51 @@ struct StackFrame {
     spans: Vec<(usize, Option<Primitive>)>,
 }
 
+/// The default maximum depth of nested function calls
+pub const DEFAULT_RECURSION_LIMIT: usize = 500;
+
+/// The stack size a thread needs to reach [`DEFAULT_RECURSION_LIMIT`] without overflowing
+///
+/// Debug builds use much more stack per call than release builds.
+/// The main thread's stack is usually too small for either, so run the interpreter on a thread with this stack size.
+pub const DEFAULT_RECURSION_STACK_SIZE: usize = if cfg!(debug_assertions) {
+    256 * 1024 * 1024
+} else {
+    32 * 1024 * 1024
+};
+
+/// Limits on the resources a program may use
+///
+/// Set them all at once with [`Uiua::with_limits`].
+#[derive(Debug, Clone)]
+pub struct ExecutionLimits {
+    /// The maximum depth of nested function calls
+    ///
+    /// Default is [`DEFAULT_RECURSION_LIMIT`]
+    pub max_call_depth: usize,
+    /// The maximum number of execution steps
+    ///
+    /// See [`Uiua::with_step_budget`]
+    pub max_steps: Option<usize>,
+    /// The maximum number of elements an array made by [`Primitive::Range`] may have
+    pub max_range_size: Option<usize>,
+    /// A flag that stops execution when set
+    ///
+    /// See [`Uiua::interrupt_handle`]
+    pub interrupt: Arc<AtomicBool>,
+}
+
+impl Default for ExecutionLimits {
+    fn default() -> Self {
+        Self {
+            max_call_depth: DEFAULT_RECURSION_LIMIT,
+            max_steps: None,
+            max_range_size: None,
+            interrupt: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
 impl Default for Uiua {
     fn default() -> Self {
         Self::with_native_sys()
@@ -172,12 +242,22 @@ impl Uiua {
             imports: Arc::new(Mutex::new(HashMap::new())),
             mode: RunMode::Normal,
             diagnostics: BTreeSet::new(),
-            backend: Arc::new(NativeSys),
+            backend: Arc::new(NativeSys::default()),
             print_diagnostics: false,
             cli_arguments: Vec::new(),
             cli_file_path: PathBuf::new(),
             execution_limit: None,
             execution_start: 0.0,
+            recursion_limit: DEFAULT_RECURSION_LIMIT,
+            call_depth: 0,
+            step_budget: None,
+            steps: 0,
+            step_budget_extended: false,
+            range_limit: None,
+            display_precision: None,
+            interrupt: Arc::new(AtomicBool::new(false)),
+            primitive_aliases: HashMap::new(),
+            parallel_each: false,
         }
     }
     /// Create a new Uiua runtime with a custom IO backend
@@ -202,6 +282,82 @@ impl Uiua {
         self.execution_limit = Some(limit.as_millis() as f64);
         self
     }
+    /// Limit the depth of nested function calls
+    ///
+    /// Default is [`DEFAULT_RECURSION_LIMIT`]
+    pub fn with_recursion_limit(mut self, limit: usize) -> Self {
+        self.recursion_limit = limit;
+        self
+    }
+    /// Limit the number of execution steps
+    ///
+    /// Every executed instruction and function call is a step.
+    /// Steps are counted across every call to [`Uiua::load_str`], not per call.
+    ///
+    /// Exceeding the budget is an error that [`Primitive::Try`] can catch.
+    /// The first time this happens, the count starts over so that the handler can run.
+    /// After the budget is exceeded a second time, every step errors,
+    /// so no more than twice the budget's steps are ever run.
+    pub fn with_step_budget(mut self, budget: usize) -> Self {
+        self.step_budget = Some(budget);
+        self
+    }
+    /// Limit the number of elements an array made by [`Primitive::Range`] may have
+    ///
+    /// Exceeding the limit is an error.
+    pub fn with_range_limit(mut self, max: usize) -> Self {
+        self.range_limit = Some(max);
+        self
+    }
+    /// Set all of the [`ExecutionLimits`] at once
+    ///
+    /// The interrupt flag replaces the current one, so handles
+    /// from [`Uiua::interrupt_handle`] should be taken afterwards.
+    pub fn with_limits(mut self, limits: ExecutionLimits) -> Self {
+        self.recursion_limit = limits.max_call_depth;
+        self.step_budget = limits.max_steps;
+        self.range_limit = limits.max_range_size;
+        self.interrupt = limits.interrupt;
+        self
+    }
+    /// Add a custom name for a primitive
+    ///
+    /// Aliases are checked before the built-in names, so they can shadow them.
+    /// Like the built-in names, they must be lowercase to be recognized.
+    pub fn with_primitive_alias(mut self, name: impl Into<String>, prim: Primitive) -> Self {
+        self.primitive_aliases.insert(name.into(), prim);
+        self
+    }
+    /// Allow [`Primitive::Each`] to run its function in parallel
+    ///
+    /// Only pure functions on large arrays are run in parallel.
+    /// Each thread gets its own copy of the runtime.
+    /// By default, each always runs sequentially.
+    pub fn with_parallel_each(mut self, parallel: bool) -> Self {
+        self.parallel_each = parallel;
+        self
+    }
+    /// Limit the number of significant digits shown when pretty printing numbers
+    ///
+    /// This affects [`SysOp::Show`](crate::SysOp::Show), [`Primitive::Trace`], and [`Primitive::Dump`].
+    /// Digits before the decimal point are never dropped.
+    /// By default, numbers are shown in full.
+    pub fn with_display_precision(mut self, digits: usize) -> Self {
+        self.display_precision = Some(digits);
+        self
+    }
+    /// Get the display precision set with [`Uiua::with_display_precision`]
+    pub fn display_precision(&self) -> Option<usize> {
+        self.display_precision
+    }
+    /// Get a handle to the interrupt flag
+    ///
+    /// Setting the flag to `true`, from any thread, makes execution stop with
+    /// [`UiuaError::Interrupted`]. Threads spawned by the program share the flag.
+    /// The flag is not cleared automatically, so it must be reset before running more code.
+    pub fn interrupt_handle(&self) -> Arc<AtomicBool> {
+        self.interrupt.clone()
+    }
     /// Set the [`RunMode`]
     ///
     /// Default is [`RunMode::Normal`]
@@ -266,7 +422,7 @@ impl Uiua {
     }
     fn load_impl(&mut self, input: &str, path: Option<&Path>) -> UiuaResult {
         self.execution_start = instant::now();
-        let (items, errors, diagnostics) = parse(input, path);
+        let (items, errors, diagnostics) = parse_with_aliases(input, path, &self.primitive_aliases);
         if self.print_diagnostics {
             for diagnostic in diagnostics {
                 println!("{}", diagnostic.show(true));
@@ -348,6 +504,16 @@ code:
         })
     }
     fn exec(&mut self, frame: StackFrame) -> UiuaResult {
+        self.step()?;
+        if self.call_depth >= self.recursion_limit {
+            return Err(self.error("Recursion limit exceeded"));
+        }
+        self.call_depth += 1;
+        let res = self.exec_frame(frame);
+        self.call_depth -= 1;
+        res
+    }
+    fn exec_frame(&mut self, frame: StackFrame) -> UiuaResult {
         let ret_height = self.scope.call.len();
         self.scope.call.push(frame);
         while self.scope.call.len() > ret_height {
@@ -495,6 +661,7 @@ code:
             } else {
                 // Go to next instruction
                 self.scope.call.last_mut().unwrap().pc += 1;
+                self.step()?;
                 if let Some(limit) = self.execution_limit {
                     if instant::now() - self.execution_start > limit {
                         return Err(UiuaError::Timeout(self.span()));
@@ -504,6 +671,37 @@ code:
         }
         Ok(())
     }
+    /// Whether execution is limited by a step budget
+    pub(crate) fn has_step_budget(&self) -> bool {
+        self.step_budget.is_some()
+    }
+    /// Whether each may run in parallel
+    pub(crate) fn parallel_each(&self) -> bool {
+        self.parallel_each
+    }
+    /// Count an execution step, erroring if the step budget is exceeded
+    pub(crate) fn step(&mut self) -> UiuaResult {
+        self.check_interrupt()?;
+        self.steps += 1;
+        match self.step_budget {
+            Some(budget) if self.steps > budget => {
+                if !self.step_budget_extended {
+                    self.step_budget_extended = true;
+                    self.steps = 0;
+                }
+                Err(self.error("Execution budget exceeded"))
+            }
+            _ => Ok(()),
+        }
+    }
+    /// Error if the interrupt flag is set
+    pub(crate) fn check_interrupt(&self) -> UiuaResult {
+        if self.interrupt.load(Ordering::Relaxed) {
+            Err(UiuaError::Interrupted(self.span()))
+        } else {
+            Ok(())
+        }
+    }
     pub(crate) fn push_span(&mut self, span: usize, prim: Option<Primitive>) {
         self.scope.call.last_mut().unwrap().spans.push((span, prim));
     }
@@ -622,6 +820,13 @@ code:
         }
         res
     }
+    /// Pop a value from the stack and convert it to a Rust type
+    ///
+    /// Errors if the stack is empty or the value cannot be converted.
+    pub fn pop_typed<T: FromValue>(&mut self, arg: impl StackArg) -> UiuaResult<T> {
+        let value = self.pop(arg)?;
+        T::from_value(value, self)
+    }
     /// Simulates popping a value and imediately pushing it back
     pub(crate) fn touch_array_stack(&mut self) {
         for bottom in &mut self.scope.array {
@@ -647,6 +852,14 @@ code:
         }
         bindings
     }
+    /// Get the value of a binding in the current scope by name
+    ///
+    /// Bindings persist after loading, so this can be used to get a function
+    /// defined by a program and then [`call`](Uiua::call) it.
+    pub fn get_binding(&self, name: &str) -> Option<Value> {
+        let index = *self.scope.names.get(name)?;
+        Some(self.globals.lock()[index].clone())
+    }
     pub fn diagnostics(&self) -> &BTreeSet<Diagnostic> {
         &self.diagnostics
     }
@@ -732,6 +945,14 @@ code:
         let n = self.scope.fills.nums.last().copied()?;
         (n.fract() == 0.0 && (0.0..=255.0).contains(&n)).then_some(n as u8)
     }
+    pub(crate) fn complex_fill(&self) -> Option<Complex> {
+        self.scope
+            .fills
+            .complexes
+            .last()
+            .copied()
+            .or_else(|| self.num_fill().map(Into::into))
+    }
     pub(crate) fn char_fill(&self) -> Option<char> {
         self.scope.fills.chars.last().copied()
     }
@@ -758,6 +979,12 @@ code:
                     set = true;
                 }
             }
+            Value::Complex(c) => {
+                if let Some(&c) = c.as_scalar() {
+                    self.scope.fills.complexes.push(c);
+                    set = true;
+                }
+            }
             Value::Char(c) => {
                 if let Some(&c) = c.as_scalar() {
                     self.scope.fills.chars.push(c);
@@ -782,6 +1009,9 @@ code:
             Value::Num(_) | Value::Byte(_) => {
                 self.scope.fills.nums.pop();
             }
+            Value::Complex(_) => {
+                self.scope.fills.complexes.pop();
+            }
             Value::Char(_) => {
                 self.scope.fills.chars.pop();
             }
@@ -826,6 +1056,16 @@ code:
             backend: self.backend.clone(),
             execution_limit: self.execution_limit,
             execution_start: self.execution_start,
+            recursion_limit: self.recursion_limit,
+            call_depth: 0,
+            step_budget: self.step_budget,
+            steps: self.steps,
+            step_budget_extended: self.step_budget_extended,
+            range_limit: self.range_limit,
+            display_precision: self.display_precision,
+            interrupt: self.interrupt.clone(),
+            primitive_aliases: self.primitive_aliases.clone(),
+            parallel_each: self.parallel_each,
         };
         self.backend
             .spawn(env, Box::new(f))
@@ -2,12 +2,13 @@ use std::{
     any::Any,
     collections::{HashMap, HashSet},
     env,
-    fs::{self, File},
-    io::{stderr, stdin, stdout, BufRead, Cursor, Read, Write},
+    fs::{self, File, OpenOptions},
+    io::{stderr, stdin, stdout, BufRead, Cursor, IsTerminal, Read, Seek, SeekFrom, Write},
     net::*,
+    path::{Component, Path, PathBuf},
     process::Command,
     sync::{
-        atomic::{self, AtomicU64},
+        atomic::{self, AtomicBool, AtomicU64},
         Arc, OnceLock,
     },
     thread::{sleep, spawn, JoinHandle},
@@ -18,14 +19,18 @@ use bufreaderwriter::seq::BufReaderWriterSeq;
 use dashmap::DashMap;
 use enum_iterator::Sequence;
 use hound::{SampleFormat, WavReader, WavSpec, WavWriter};
-use image::{DynamicImage, ImageOutputFormat};
+use image::{DynamicImage, ImageFormat, ImageOutputFormat};
 use once_cell::sync::Lazy;
 use parking_lot::Mutex;
 use tinyvec::tiny_vec;
 
 use crate::{
-    array::Array, cowslice::CowSlice, function::Function, grid_fmt::GridFmt, primitive::PrimDoc,
-    value::Value, Uiua, UiuaError, UiuaResult,
+    array::Array,
+    cowslice::CowSlice,
+    function::Function,
+    primitive::{seed_random, PrimDoc},
+    value::Value,
+    Uiua, UiuaError, UiuaResult,
 };
 
 pub fn example_ua<T>(f: impl FnOnce(&mut String) -> T) -> T {
@@ -104,23 +109,60 @@ sys_op! {
     (1(0), Prin, "&pf", "print and flush"),
     /// Print a value to stdout followed by a newline
     (1(0), Print, "&p", "print with newline"),
+    /// Flush stdout
+    ///
+    /// This makes sure that everything printed so far is visible.
+    /// Use it to show a prompt before reading input with [&sc].
+    /// ex: &sc &flush &pf "Name: "
+    ///
+    /// The native interpreter already flushes after every print, but other environments may not.
+    (0(0), Flush, "&flush", "flush stdout"),
     /// Read a line from stdin
     ///
     /// The normal output is a string.
     /// If EOF is reached, the number `0` is returned instead.
     /// Programs that wish to properly handle EOF should check for this.
     (0, ScanLine, "&sc", "scan line"),
+    /// Get the next key press without waiting
+    ///
+    /// Keys that produce a character return that character.
+    /// Enter and tab return `@\n` and `@\t`. Backspace and escape return the characters with codes `8` and `27`.
+    /// Other special keys return their name as a string, like `"up"`, `"pageup"`, or `"f1"`.
+    /// If no key has been pressed, an empty string is returned.
+    ///
+    /// The terminal is only put in raw mode while checking for a key press.
+    /// This is not supported on the website.
+    ///
+    /// ex: &getkey
+    /// ex: =0⧻&getkey
+    (0, GetKey, "&getkey", "get key"),
     /// Get the size of the terminal
     ///
     /// The result is a 2-element array of the height and width of the terminal.
     /// Height comes first so that the array can be used as a shape in [reshape].
     (0, TermSize, "&ts", "terminal size"),
+    /// Check if a standard stream is a terminal
+    ///
+    /// Expects `0` for stdin, `1` for stdout, or `2` for stderr.
+    /// Returns `1` if the stream is a terminal and `0` if it is redirected, such as to a file or pipe.
+    /// This is useful for disabling colors or progress output when the output is not being viewed directly.
+    /// On the web, this is always `0`.
+    /// ex: &isatty 1
+    (1, IsTty, "&isatty", "is terminal"),
     /// Get the command line arguments
     ///
     /// The first element will always be the name of your script
     (0, Args, "&args", "arguments"),
     /// Get the value of an environment variable
     (1, Var, "&var", "environment variable"),
+    /// Get all environment variables
+    ///
+    /// The result is a rank `2` array of [box]ed strings.
+    /// Each row is a `[name value]` pair. Rows are sorted by name.
+    ///
+    /// ex: &env
+    /// ex: ⊢⍉ &env
+    (0, EnvAll, "&env", "environment variables"),
     /// Run a command and wait for it to finish
     ///
     /// Standard IO will be inherited.
@@ -140,6 +182,63 @@ sys_op! {
     /// On the web, this example will hang for 1 second.
     /// ex: ⚂ &sl 1
     (1(0), Sleep, "&sl", "sleep"),
+    /// Exit the program with a status code
+    ///
+    /// Expects a natural number.
+    /// ex: &exit 1
+    ///
+    /// Wherever Uiua is embedded, this stops the program with an error the host can handle instead of ending the whole process.
+    (1(0), Exit, "&exit", "exit"),
+    /// Get the CPU time used by the process in seconds
+    ///
+    /// Unlike [now], this does not advance while the process is sleeping or waiting.
+    /// This makes it useful for benchmarking.
+    /// On the web, this is the same as [now].
+    /// ex: &cputime
+    (0, CpuTime, "&cputime", "cpu time"),
+    /// Get the total and available system memory in bytes
+    ///
+    /// Returns a 2-element array.
+    /// On the web, this is `[0 0]`.
+    /// ex: &sysmem
+    (0, SystemMemory, "&sysmem", "system memory"),
+    /// Get the name of the current system backend
+    ///
+    /// This is `"native"` for the native interpreter and `"web"` on the website.
+    /// Programs can use it to skip features that are not supported in some environments.
+    /// ex: &backend
+    (0, BackendName, "&backend", "backend name"),
+    /// Start a timer
+    ///
+    /// Returns a handle that can be passed to [&elapsed].
+    /// Timers use a monotonic clock, so they are not affected by changes to the system time.
+    /// Use [&cl] to discard a timer.
+    /// ex: &timer
+    ///
+    /// See also: [&elapsed]
+    (0, TimerStart, "&timer", "timer - start"),
+    /// Get the number of seconds since a timer was started
+    ///
+    /// Expects a handle returned by [&timer].
+    /// ex: &elapsed ⊙(&sl 0.5) &timer
+    (1, TimerElapsed, "&elapsed", "timer - elapsed"),
+    /// Get the number of seconds since the last call to [&dt]
+    ///
+    /// The first call returns `0`.
+    /// This is useful for making animations run at the same speed regardless of frame rate.
+    /// Here, a position moves `10` units per second.
+    /// ex: ⍥(&sl 0.05 &p . +×10 &dt) 20 0
+    ///
+    /// See also: [&timer]
+    (0, FrameDelta, "&dt", "frame delta time"),
+    /// Seed the random number generator
+    ///
+    /// Expects a number.
+    /// After seeding, [random] and [randarray] produce the same sequence of numbers every time the program runs.
+    /// The seed only affects the current thread.
+    /// [gen] and [deal] are not affected, since they take their own seeds.
+    /// ex: ≅ [⍥⚂3] &seed 0 [⍥⚂3] &seed 0
+    (1(0), Seed, "&seed", "seed random"),
     /// Read at most n bytes from a stream
     (2, ReadStr, "&rs", "read to string"),
     /// Read at most n bytes from a stream
@@ -177,6 +276,13 @@ sys_op! {
     (1, FListDir, "&fld", "file - list directory"),
     /// Check if a path is a file
     (1, FIsFile, "&fif", "file - is file"),
+    /// Wait until a file changes
+    ///
+    /// Expects a path.
+    /// Blocks until the file's modification time or size changes, or until it is removed.
+    /// This can be used to rerun something whenever a file is saved.
+    /// ex: ⍥(&p "Changed!" &fwatch "example.txt")∞
+    (1(0), FWatch, "&fwatch", "file - watch"),
     /// Read all the contents of a file into a string
     ///
     /// Expects a path and returns a [rank]`1` character array.
@@ -185,10 +291,22 @@ sys_op! {
     ///
     /// Expects a path and returns a [rank]`1` numeric array.
     (1, FReadAllBytes, "&frab", "file - read all to bytes"),
+    /// Read a range of bytes from a file
+    ///
+    /// Expects a path, an offset, and a length, and returns a [rank]`1` byte array.
+    /// If the range goes past the end of the file, only the bytes that are there are returned.
+    /// ex: &fread "example.txt" 10 5
+    (3, FReadRange, "&fread", "file - read range"),
     /// Write the entire contents of an array to a file
     ///
     /// Expects a path and a [rank]`1` array or either numbers or characters.
     (2(0), FWriteAll, "&fwa", "file - write all"),
+    /// Append the contents of an array to the end of a file
+    ///
+    /// Expects a path and a [rank]`1` array of either numbers or characters.
+    /// The file is created if it does not exist.
+    /// ex: &fappend "log.txt" "Started\n"
+    (2(0), FAppend, "&fappend", "file - append"),
     /// Decode an image from a byte array
     ///
     /// Supported formats are `jpg`, `png`, `bmp`, `gif`, and `ico`.
@@ -232,6 +350,20 @@ sys_op! {
     ///
     /// See also: [&ime]
     (1(0), ImShow, "&ims", "image - show"),
+    /// Write an image to a PNG file
+    ///
+    /// Expects a path and an image.
+    /// The image must be a numeric array with shape `[h w]` for grayscale, `[h w 3]` for RGB, or `[h w 4]` for RGBA.
+    /// If all values are between `0` and `1`, they are scaled to `0` to `255`. Otherwise, they are used as-is.
+    /// ex: &imgwrite "gradient.png" ÷200 ⊞+.⇡100
+    (2(0), ImWrite, "&imgwrite", "image - write"),
+    /// Read an image from a file
+    ///
+    /// Expects a path and returns a numeric array with values between `0` and `1`.
+    /// Grayscale images have shape `[h w]`. Other images have shape `[h w c]`, where `c` is the number of channels.
+    /// Supported formats are `jpg`, `png`, `bmp`, `gif`, and `ico`.
+    /// ex: △ &imgread "photo.png"
+    (1, ImRead, "&imgread", "image - read"),
     /// Encode a gif into a byte array
     ///
     /// The first argument is a framerate in seconds.
@@ -330,6 +462,93 @@ sys_op! {
     /// - The HTTP version
     /// - The `Host` header (if not defined)
     (2, HttpsWrite, "&httpsw", "http - Make an HTTP request"),
+    /// Decode a JSON string into a value
+    ///
+    /// Numbers and strings are decoded naturally. Booleans become `0` or `1`.
+    /// `null` becomes `NaN`.
+    /// ex: &jsonde "[1, 2.5, true, null]"
+    /// Arrays whose items all have the same shape become normal arrays.
+    /// Otherwise, the items are [box]ed.
+    /// ex: &jsonde "[[1, 2], [3, 4]]"
+    /// ex: &jsonde "[\"cat\", \"horse\"]"
+    /// Objects become rank `2` arrays of [box]ed `[key value]` pairs, sorted by key.
+    /// ex: &jsonde "{\"name\": \"Uiua\", \"tags\": [\"array\", \"stack\"]}"
+    ///
+    /// See also: [&jsonen]
+    (1, JsonDecode, "&jsonde", "json - decode"),
+    /// Encode a value as a JSON string
+    ///
+    /// This is the inverse of [&jsonde].
+    /// ex: &jsonen [1_2 3_4]
+    /// ex: &jsonen {"cat" 5 [1 2]}
+    /// Rank `2` arrays of [box]ed pairs whose first column is all strings are encoded as objects.
+    /// ex: &jsonen [{"a" 1} {"b" "two"}]
+    /// ex: &jsonen &jsonde "{\"x\": [1, {\"y\": null}]}"
+    ///
+    /// See also: [&jsonde]
+    (1, JsonEncode, "&jsonen", "json - encode"),
+    /// Get the SHA-256 digest of a string or byte array
+    ///
+    /// Strings are encoded as UTF-8 before hashing.
+    /// The result is a list of `32` bytes.
+    /// ex: &hash "hello"
+    /// ex: ≅ &hash "abc" &hash [97 98 99]
+    (1, Hash, "&hash", "hash - sha256"),
+    /// Generate cryptographically secure random bytes
+    ///
+    /// Expects a number of bytes and returns a [rank]`1` byte array of that length.
+    /// Unlike [random] and [gen], the bytes come from the operating system and cannot be seeded.
+    /// This makes them suitable for things like tokens and keys.
+    /// ex: &csrand 16
+    (1, SecureRandom, "&csrand", "cryptographically secure random bytes"),
+    /// Encode a string or byte array as base64
+    ///
+    /// Strings are encoded as UTF-8 first.
+    /// The result is a string using the standard base64 alphabet with padding.
+    /// ex: &b64en "Uiua"
+    /// ex: &b64en [0 255 128]
+    ///
+    /// See also: [&b64de]
+    (1, Base64Encode, "&b64en", "base64 - encode"),
+    /// Decode a base64 string into a byte array
+    ///
+    /// This is the inverse of [&b64en].
+    /// ex: &b64de "VWl1YQ=="
+    /// ex: &b64de &b64en [1 2 3]
+    /// Invalid base64 is an error.
+    /// ex! &b64de "VW!1YQ=="
+    ///
+    /// See also: [&b64en]
+    (1, Base64Decode, "&b64de", "base64 - decode"),
+    /// Decode a CSV string into a list of [box]ed records
+    ///
+    /// Each record is a list of [box]ed field strings.
+    /// Quoted fields may contain commas, newlines, and doubled quotes.
+    /// ex: &csvde "name,age\nAlice,30\nBob,25"
+    /// ex: &csvde "\"Hello, World\",\"say \"\"hi\"\"\"\n"
+    ///
+    /// See also: [&csven]
+    (1, CsvDecode, "&csvde", "csv - decode"),
+    /// Encode a list of records as a CSV string
+    ///
+    /// This is the inverse of [&csvde].
+    /// Each row of the array is a record, and each row of a record is a field.
+    /// Records and fields may be [box]ed. Fields must be strings or scalar numbers.
+    /// Fields that contain commas, quotes, or newlines are quoted.
+    /// ex: &csven {{"name" "age"} {"Alice" 30}}
+    /// ex: &csven [{"a" "b, c"} {"d" "e"}]
+    ///
+    /// See also: [&csvde]
+    (1, CsvEncode, "&csven", "csv - encode"),
+}
+
+/// A key press read by [`SysBackend::get_key`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Key {
+    /// A key that produces a character
+    Char(char),
+    /// A special key, like `up` or `f1`
+    Named(String),
 }
 
 /// A handle to an IO stream
@@ -364,8 +583,22 @@ type AudioStreamFn = Box<dyn FnMut(Vec<f64>) -> UiuaResult<Vec<[f64; 2]>> + Send
 #[allow(unused_variables)]
 pub trait SysBackend: Any + Send + Sync + 'static {
     fn any(&self) -> &dyn Any;
+    /// The name of the backend, as returned by [`SysOp::BackendName`]
+    fn name(&self) -> &str {
+        "custom"
+    }
     /// Save a color-formatted version of an error message for later printing
     fn save_error_color(&self, error: &UiuaError) {}
+    /// Whether [`SysBackend::report_progress`] should be called
+    ///
+    /// This is checked once at the start of each long-running loop.
+    fn reports_progress(&self) -> bool {
+        false
+    }
+    /// Report the progress of a loop like [`Primitive::Each`] or [`Primitive::Rows`]
+    ///
+    /// This is called every so often with the number of iterations done and the total.
+    fn report_progress(&self, done: usize, total: usize) {}
     fn print_str_stdout(&self, s: &str) -> Result<(), String> {
         Err("Printing to stdout is not supported in this environment".into())
     }
@@ -376,18 +609,40 @@ pub trait SysBackend: Any + Send + Sync + 'static {
         eprint!("{s}");
         _ = stderr().flush();
     }
+    /// Flush stdout
+    ///
+    /// Backends that do not buffer stdout do not need to implement this.
+    fn flush(&self) -> Result<(), String> {
+        Ok(())
+    }
     /// Read a line from stdin
     ///
     /// Should return `Ok(None)` if EOF is reached.
     fn scan_line_stdin(&self) -> Result<Option<String>, String> {
         Err("Reading from stdin is not supported in this environment".into())
     }
+    /// Get the next key press without waiting
+    ///
+    /// Should return `Ok(None)` if no key has been pressed.
+    fn get_key(&self) -> Result<Option<Key>, String> {
+        Err("Reading key presses is not supported in this environment".into())
+    }
     fn var(&self, name: &str) -> Option<String> {
         None
     }
+    /// Get all environment variables as `(name, value)` pairs
+    fn env_vars(&self) -> Result<Vec<(String, String)>, String> {
+        Err("Getting environment variables is not supported in this environment".into())
+    }
     fn term_size(&self) -> Result<(usize, usize), String> {
         Err("Getting the terminal size is not supported in this environment".into())
     }
+    /// Check if a standard stream is a terminal
+    ///
+    /// `0` is stdin, `1` is stdout, and `2` is stderr.
+    fn is_tty(&self, stream: usize) -> bool {
+        false
+    }
     fn file_exists(&self, path: &str) -> bool {
         false
     }
@@ -397,6 +652,12 @@ pub trait SysBackend: Any + Send + Sync + 'static {
     fn is_file(&self, path: &str) -> Result<bool, String> {
         Err("This IO operation is not supported in this environment".into())
     }
+    /// Block until the file at a path changes
+    ///
+    /// Should return early if the `interrupt` flag is set.
+    fn watch_file(&self, path: &str, interrupt: &AtomicBool) -> Result<(), String> {
+        Err("Watching files is not supported in this environment".into())
+    }
     fn read(&self, handle: Handle, count: usize) -> Result<Vec<u8>, String> {
         Err("This IO operation is not supported in this environment".into())
     }
@@ -429,18 +690,73 @@ pub trait SysBackend: Any + Send + Sync + 'static {
         self.close(handle)?;
         Ok(bytes)
     }
+    /// Read at most `len` bytes from a file, starting at `offset`
+    ///
+    /// Offsets past the end of the file return no bytes.
+    fn file_read_range(&self, path: &str, offset: usize, len: usize) -> Result<Vec<u8>, String> {
+        let bytes = self.file_read_all(path)?;
+        let start = offset.min(bytes.len());
+        let end = start.saturating_add(len).min(bytes.len());
+        Ok(bytes[start..end].to_vec())
+    }
     fn file_write_all(&self, path: &str, contents: &[u8]) -> Result<(), String> {
         let handle = self.create_file(path)?;
         self.write(handle, contents)?;
         self.close(handle)?;
         Ok(())
     }
+    /// Write bytes to the end of a file, creating it if it does not exist
+    fn file_append(&self, path: &str, contents: &[u8]) -> Result<(), String> {
+        let mut bytes = self.file_read_all(path).unwrap_or_default();
+        bytes.extend_from_slice(contents);
+        self.file_write_all(path, &bytes)
+    }
     fn sleep(&self, seconds: f64) -> Result<(), String> {
         Err("Sleeping is not supported in this environment".into())
     }
+    /// Exit the process with a status code
+    ///
+    /// If this returns `Ok`, the program stops with [`UiuaError::Exit`] instead.
+    /// This is the default, so embedders can handle exiting without ending the host process.
+    fn exit(&self, status: i32) -> Result<(), String> {
+        Ok(())
+    }
+    /// Get the CPU time used by the process in seconds
+    fn cpu_time(&self) -> Result<f64, String> {
+        Err("Getting CPU time is not supported in this environment".into())
+    }
+    /// Get `n` cryptographically secure random bytes
+    fn secure_random(&self, n: usize) -> Result<Vec<u8>, String> {
+        Err("Secure random bytes are not supported in this environment".into())
+    }
+    /// Get the total and available system memory in bytes
+    fn system_memory(&self) -> Result<(u64, u64), String> {
+        Err("Getting system memory is not supported in this environment".into())
+    }
+    /// Start a timer and get a handle to it
+    fn timer_start(&self) -> Result<Handle, String> {
+        Err("Timers are not supported in this environment".into())
+    }
+    /// Get the number of seconds since a timer was started
+    fn timer_elapsed(&self, handle: Handle) -> Result<f64, String> {
+        Err("Timers are not supported in this environment".into())
+    }
+    /// Get the number of seconds since this was last called, or `0` on the first call
+    fn frame_delta(&self) -> Result<f64, String> {
+        Err("Frame timing is not supported in this environment".into())
+    }
     fn show_image(&self, image: DynamicImage) -> Result<(), String> {
         Err("Showing images not supported in this environment".into())
     }
+    /// Write an image to a PNG file
+    fn write_image(&self, path: &str, image: DynamicImage) -> Result<(), String> {
+        Err("Writing images is not supported in this environment".into())
+    }
+    /// Read and decode an image file
+    fn read_image(&self, path: &str) -> Result<DynamicImage, String> {
+        let bytes = self.file_read_all(path)?;
+        image::load_from_memory(&bytes).map_err(|e| format!("Failed to read image: {e}"))
+    }
     fn show_gif(&self, gif_bytes: Vec<u8>) -> Result<(), String> {
         Err("Showing gifs not supported in this environment".into())
     }
@@ -515,8 +831,59 @@ pub trait SysBackend: Any + Send + Sync + 'static {
     }
 }
 
+/// The default [`SysBackend`], which uses the native file system, network, and terminal
 #[derive(Default)]
-pub struct NativeSys;
+pub struct NativeSys {
+    base_dir: Option<PathBuf>,
+    allow_outside_base: bool,
+    last_frame: Mutex<Option<std::time::Instant>>,
+}
+
+impl NativeSys {
+    /// Create a native backend whose file operations are resolved relative to a base directory
+    ///
+    /// By default, absolute paths and paths that leave the base directory are rejected.
+    /// Use [`NativeSys::allow_outside_base_dir`] to permit them.
+    pub fn with_base_dir(base_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            base_dir: Some(base_dir.into()),
+            ..Default::default()
+        }
+    }
+    /// Set whether paths outside the base directory are allowed
+    pub fn allow_outside_base_dir(mut self, allow: bool) -> Self {
+        self.allow_outside_base = allow;
+        self
+    }
+    /// Get the base directory, if one is set
+    pub fn base_dir(&self) -> Option<&Path> {
+        self.base_dir.as_deref()
+    }
+    fn resolve_path(&self, path: &str) -> Result<PathBuf, String> {
+        let Some(base_dir) = &self.base_dir else {
+            return Ok(path.into());
+        };
+        let path = Path::new(path);
+        if !self.allow_outside_base {
+            if path.is_absolute() {
+                return Err(format!(
+                    "Absolute path {} is not allowed",
+                    path.to_string_lossy()
+                ));
+            }
+            if path
+                .components()
+                .any(|c| matches!(c, Component::ParentDir | Component::Prefix(_)))
+            {
+                return Err(format!(
+                    "Path {} leaves the base directory",
+                    path.to_string_lossy()
+                ));
+            }
+        }
+        Ok(base_dir.join(path))
+    }
+}
 
 type Buffered<T> = BufReaderWriterSeq<T>;
 
@@ -527,6 +894,7 @@ struct GlobalNativeSys {
     tcp_sockets: DashMap<Handle, Buffered<TcpStream>>,
     hostnames: DashMap<Handle, String>,
     threads: DashMap<Handle, JoinHandle<UiuaResult<Vec<Value>>>>,
+    timers: DashMap<Handle, std::time::Instant>,
     #[cfg(feature = "audio")]
     audio_stream_time: Mutex<Option<f64>>,
     #[cfg(feature = "audio")]
@@ -549,6 +917,7 @@ impl Default for GlobalNativeSys {
             tcp_sockets: DashMap::new(),
             hostnames: DashMap::new(),
             threads: DashMap::new(),
+            timers: DashMap::new(),
             #[cfg(feature = "audio")]
             audio_stream_time: Mutex::new(None),
             #[cfg(feature = "audio")]
@@ -565,6 +934,7 @@ impl GlobalNativeSys {
             if !self.files.contains_key(&handle)
                 && !self.tcp_listeners.contains_key(&handle)
                 && !self.tcp_sockets.contains_key(&handle)
+                && !self.timers.contains_key(&handle)
             {
                 return handle;
             }
@@ -586,6 +956,12 @@ impl GlobalNativeSys {
 
 static NATIVE_SYS: Lazy<GlobalNativeSys> = Lazy::new(Default::default);
 
+/// Flush and close all files and sockets opened by the native backend
+pub fn close_native_streams() {
+    NATIVE_SYS.files.clear();
+    NATIVE_SYS.tcp_sockets.clear();
+}
+
 #[cfg(feature = "audio")]
 pub fn set_audio_stream_time(time: f64) {
     *NATIVE_SYS.audio_stream_time.lock() = Some(time);
@@ -603,6 +979,9 @@ impl SysBackend for NativeSys {
     fn any(&self) -> &dyn Any {
         self
     }
+    fn name(&self) -> &str {
+        "native"
+    }
     fn print_str_stdout(&self, s: &str) -> Result<(), String> {
         let mut stdout = stdout().lock();
         stdout.write_all(s.as_bytes()).map_err(|e| e.to_string())?;
@@ -613,6 +992,9 @@ impl SysBackend for NativeSys {
         stderr.write_all(s.as_bytes()).map_err(|e| e.to_string())?;
         stderr.flush().map_err(|e| e.to_string())
     }
+    fn flush(&self) -> Result<(), String> {
+        stdout().flush().map_err(|e| e.to_string())
+    }
     fn scan_line_stdin(&self) -> Result<Option<String>, String> {
         stdin()
             .lock()
@@ -626,41 +1008,147 @@ impl SysBackend for NativeSys {
             .colored_errors
             .insert(error.message(), error.show(true));
     }
+    #[cfg(feature = "terminal_input")]
+    fn get_key(&self) -> Result<Option<Key>, String> {
+        use crossterm::{
+            event::{self, Event, KeyCode, KeyEventKind},
+            terminal,
+        };
+        fn next_key() -> std::io::Result<Option<Key>> {
+            while event::poll(Duration::ZERO)? {
+                let Event::Key(key) = event::read()? else {
+                    continue;
+                };
+                if key.kind == KeyEventKind::Release {
+                    continue;
+                }
+                let key = match key.code {
+                    KeyCode::Char(c) => Key::Char(c),
+                    KeyCode::Enter => Key::Char('\n'),
+                    KeyCode::Tab => Key::Char('\t'),
+                    KeyCode::Backspace => Key::Char('\x08'),
+                    KeyCode::Esc => Key::Char('\x1b'),
+                    KeyCode::Up => Key::Named("up".into()),
+                    KeyCode::Down => Key::Named("down".into()),
+                    KeyCode::Left => Key::Named("left".into()),
+                    KeyCode::Right => Key::Named("right".into()),
+                    KeyCode::Home => Key::Named("home".into()),
+                    KeyCode::End => Key::Named("end".into()),
+                    KeyCode::PageUp => Key::Named("pageup".into()),
+                    KeyCode::PageDown => Key::Named("pagedown".into()),
+                    KeyCode::Delete => Key::Named("delete".into()),
+                    KeyCode::Insert => Key::Named("insert".into()),
+                    KeyCode::BackTab => Key::Named("backtab".into()),
+                    KeyCode::F(n) => Key::Named(format!("f{n}")),
+                    _ => continue,
+                };
+                return Ok(Some(key));
+            }
+            Ok(None)
+        }
+        terminal::enable_raw_mode().map_err(|e| format!("Failed to enable raw mode: {e}"))?;
+        let key = next_key();
+        terminal::disable_raw_mode().map_err(|e| format!("Failed to disable raw mode: {e}"))?;
+        key.map_err(|e| format!("Failed to read key: {e}"))
+    }
     fn term_size(&self) -> Result<(usize, usize), String> {
         let (w, h) = term_size::dimensions().ok_or("Failed to get terminal size")?;
         Ok((w, h.saturating_sub(1)))
     }
+    fn is_tty(&self, stream: usize) -> bool {
+        match stream {
+            0 => stdin().is_terminal(),
+            1 => stdout().is_terminal(),
+            2 => stderr().is_terminal(),
+            _ => false,
+        }
+    }
     fn var(&self, name: &str) -> Option<String> {
         env::var(name).ok()
     }
+    fn env_vars(&self) -> Result<Vec<(String, String)>, String> {
+        let mut vars: Vec<(String, String)> = env::vars_os()
+            .map(|(name, value)| {
+                (
+                    name.to_string_lossy().into(),
+                    value.to_string_lossy().into(),
+                )
+            })
+            .collect();
+        vars.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+        Ok(vars)
+    }
     fn file_exists(&self, path: &str) -> bool {
-        fs::metadata(path).is_ok()
+        self.resolve_path(path)
+            .is_ok_and(|path| fs::metadata(path).is_ok())
     }
     fn is_file(&self, path: &str) -> Result<bool, String> {
-        fs::metadata(path)
+        fs::metadata(self.resolve_path(path)?)
             .map(|m| m.is_file())
             .map_err(|e| e.to_string())
     }
+    fn watch_file(&self, path: &str, interrupt: &AtomicBool) -> Result<(), String> {
+        let path = self.resolve_path(path)?;
+        // Only the metadata is compared, so large files are not read on every poll
+        let snapshot = |path: &Path| {
+            let metadata = fs::metadata(path).ok()?;
+            Some((metadata.modified().ok()?, metadata.len()))
+        };
+        let initial = snapshot(&path).ok_or_else(|| format!("Cannot watch {}", path.display()))?;
+        loop {
+            sleep(Duration::from_millis(100));
+            if interrupt.load(atomic::Ordering::Relaxed)
+                || snapshot(&path).as_ref() != Some(&initial)
+            {
+                return Ok(());
+            }
+        }
+    }
     fn list_dir(&self, path: &str) -> Result<Vec<String>, String> {
         let mut paths = Vec::new();
-        for entry in fs::read_dir(path).map_err(|e| e.to_string())? {
+        for entry in fs::read_dir(self.resolve_path(path)?).map_err(|e| e.to_string())? {
             let entry = entry.map_err(|e| e.to_string())?;
-            paths.push(entry.path().to_string_lossy().into());
+            let path = entry.path();
+            let path = match &self.base_dir {
+                Some(base_dir) => path.strip_prefix(base_dir).unwrap_or(&path),
+                None => &path,
+            };
+            paths.push(path.to_string_lossy().into());
         }
         Ok(paths)
     }
     fn open_file(&self, path: &str) -> Result<Handle, String> {
+        let path = self.resolve_path(path)?;
         let handle = NATIVE_SYS.new_handle();
         let file = File::open(path).map_err(|e| e.to_string())?;
         NATIVE_SYS.files.insert(handle, Buffered::new_reader(file));
         Ok(handle)
     }
+    fn file_read_range(&self, path: &str, offset: usize, len: usize) -> Result<Vec<u8>, String> {
+        let mut file = File::open(self.resolve_path(path)?).map_err(|e| e.to_string())?;
+        file.seek(SeekFrom::Start(offset as u64))
+            .map_err(|e| e.to_string())?;
+        let mut bytes = Vec::new();
+        file.take(len as u64)
+            .read_to_end(&mut bytes)
+            .map_err(|e| e.to_string())?;
+        Ok(bytes)
+    }
     fn create_file(&self, path: &str) -> Result<Handle, String> {
+        let path = self.resolve_path(path)?;
         let handle = NATIVE_SYS.new_handle();
         let file = File::create(path).map_err(|e| e.to_string())?;
         NATIVE_SYS.files.insert(handle, Buffered::new_writer(file));
         Ok(handle)
     }
+    fn file_append(&self, path: &str, contents: &[u8]) -> Result<(), String> {
+        let mut file = OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(self.resolve_path(path)?)
+            .map_err(|e| e.to_string())?;
+        file.write_all(contents).map_err(|e| e.to_string())
+    }
     fn read(&self, handle: Handle, len: usize) -> Result<Vec<u8>, String> {
         Ok(match NATIVE_SYS.get_stream(handle)? {
             SysStream::File(mut file) => {
@@ -704,6 +1192,47 @@ impl SysBackend for NativeSys {
         sleep(Duration::from_secs_f64(seconds));
         Ok(())
     }
+    #[cfg(unix)]
+    fn cpu_time(&self) -> Result<f64, String> {
+        let mut time = libc::timespec {
+            tv_sec: 0,
+            tv_nsec: 0,
+        };
+        // Safety: `time` is a valid timespec for the duration of the call
+        if unsafe { libc::clock_gettime(libc::CLOCK_PROCESS_CPUTIME_ID, &mut time) } != 0 {
+            return Err(std::io::Error::last_os_error().to_string());
+        }
+        Ok(time.tv_sec as f64 + time.tv_nsec as f64 / 1e9)
+    }
+    #[cfg(feature = "getrandom")]
+    fn secure_random(&self, n: usize) -> Result<Vec<u8>, String> {
+        let mut bytes = vec![0; n];
+        getrandom::getrandom(&mut bytes).map_err(|e| e.to_string())?;
+        Ok(bytes)
+    }
+    #[cfg(feature = "sysinfo")]
+    fn system_memory(&self) -> Result<(u64, u64), String> {
+        let mut system = sysinfo::System::new();
+        system.refresh_memory();
+        Ok((system.total_memory(), system.available_memory()))
+    }
+    fn timer_start(&self) -> Result<Handle, String> {
+        let handle = NATIVE_SYS.new_handle();
+        NATIVE_SYS.timers.insert(handle, std::time::Instant::now());
+        Ok(handle)
+    }
+    fn timer_elapsed(&self, handle: Handle) -> Result<f64, String> {
+        let start = NATIVE_SYS
+            .timers
+            .get(&handle)
+            .ok_or_else(|| "Invalid timer handle".to_string())?;
+        Ok(start.elapsed().as_secs_f64())
+    }
+    fn frame_delta(&self) -> Result<f64, String> {
+        let now = std::time::Instant::now();
+        let last = self.last_frame.lock().replace(now);
+        Ok(last.map_or(0.0, |last| (now - last).as_secs_f64()))
+    }
     #[cfg(feature = "terminal_image")]
     fn show_image(&self, image: DynamicImage) -> Result<(), String> {
         let (width, height) = if let Some((w, h)) = term_size::dimensions() {
@@ -733,6 +1262,12 @@ impl SysBackend for NativeSys {
         .map(drop)
         .map_err(|e| format!("Failed to show image: {e}"))
     }
+    fn write_image(&self, path: &str, image: DynamicImage) -> Result<(), String> {
+        let path = self.resolve_path(path)?;
+        image
+            .save_with_format(path, ImageFormat::Png)
+            .map_err(|e| format!("Failed to write image: {e}"))
+    }
     #[cfg(feature = "audio")]
     fn play_audio(&self, wav_bytes: Vec<u8>) -> Result<(), String> {
         use hodaun::*;
@@ -898,6 +1433,7 @@ impl SysBackend for NativeSys {
     }
     fn close(&self, handle: Handle) -> Result<(), String> {
         if NATIVE_SYS.files.remove(&handle).is_some()
+            || NATIVE_SYS.timers.remove(&handle).is_some()
             || NATIVE_SYS.tcp_listeners.remove(&handle).is_some()
             || (NATIVE_SYS.tcp_sockets.remove(&handle).is_some()
                 && NATIVE_SYS.hostnames.remove(&handle).is_some())
@@ -955,6 +1491,9 @@ impl SysBackend for NativeSys {
         ))
     }
     fn change_directory(&self, path: &str) -> Result<(), String> {
+        if self.base_dir.is_some() {
+            return Err("Changing directories is not supported with a base directory".into());
+        }
         env::set_current_dir(path).map_err(|e| e.to_string())
     }
     #[cfg(feature = "https")]
@@ -1098,7 +1637,7 @@ impl SysOp {
     pub(crate) fn run(&self, env: &mut Uiua) -> UiuaResult {
         match self {
             SysOp::Show => {
-                let s = env.pop(1)?.grid_string();
+                let s = env.pop(1)?.show_with_precision(env.display_precision());
                 env.backend.print_str_stdout(&s).map_err(|e| env.error(e))?;
                 env.backend
                     .print_str_stdout("\n")
@@ -1110,6 +1649,7 @@ impl SysOp {
                     .print_str_stdout(&val.to_string())
                     .map_err(|e| env.error(e))?;
             }
+            SysOp::Flush => env.backend.flush().map_err(|e| env.error(e))?,
             SysOp::Print => {
                 let val = env.pop(1)?;
                 env.backend
@@ -1126,10 +1666,27 @@ impl SysOp {
                     env.push(0u8);
                 }
             }
+            SysOp::GetKey => match env.backend.get_key().map_err(|e| env.error(e))? {
+                Some(Key::Char(c)) => env.push(c),
+                Some(Key::Named(name)) => env.push(name),
+                None => env.push(String::new()),
+            },
             SysOp::TermSize => {
                 let (width, height) = env.backend.term_size().map_err(|e| env.error(e))?;
                 env.push(vec![height as f64, width as f64])
             }
+            SysOp::Seed => {
+                let seed = env.pop(1)?.as_num(env, "Seed must be a number")?;
+                seed_random(seed.to_bits());
+            }
+            SysOp::IsTty => {
+                let stream = env.pop(1)?.as_nat(env, "Stream must be 0, 1, or 2")?;
+                if stream > 2 {
+                    return Err(env.error("Stream must be 0, 1, or 2"));
+                }
+                let is_tty = env.backend.is_tty(stream);
+                env.push(is_tty);
+            }
             SysOp::Args => {
                 let mut args = Vec::new();
                 args.push(env.file_path().to_string_lossy().into_owned());
@@ -1143,6 +1700,16 @@ impl SysOp {
                 let var = env.backend.var(&key).unwrap_or_default();
                 env.push(var);
             }
+            SysOp::EnvAll => {
+                let vars = env.backend.env_vars().map_err(|e| env.error(e))?;
+                let shape = tiny_vec![vars.len(), 2];
+                let data: CowSlice<_> = vars
+                    .into_iter()
+                    .flat_map(|(name, value)| [name, value])
+                    .map(|s| Arc::new(Function::constant(s)))
+                    .collect();
+                env.push(Array::new(shape, data));
+            }
             SysOp::FOpen => {
                 let path = env.pop(1)?.as_string(env, "Path must be a string")?;
                 let handle = env.backend.open_file(&path).map_err(|e| env.error(e))?;
@@ -1270,6 +1837,9 @@ impl SysOp {
                 let bytes: Vec<u8> = match data {
                     Value::Num(arr) => arr.data.iter().map(|&x| x as u8).collect(),
                     Value::Byte(arr) => arr.data.into(),
+                    Value::Complex(_) => {
+                        return Err(env.error("Cannot write complex array to file"))
+                    }
                     Value::Char(arr) => arr.data.iter().collect::<String>().into(),
                     Value::Func(_) => return Err(env.error("Cannot write function array to file")),
                 };
@@ -1321,15 +1891,26 @@ impl SysOp {
                 let bytes = bytes.into_iter().map(Into::into);
                 env.push(Array::<u8>::from_iter(bytes));
             }
+            SysOp::FReadRange => {
+                let path = env.pop(1)?.as_string(env, "Path must be a string")?;
+                let offset = env.pop(2)?.as_nat(env, "Offset must be a natural number")?;
+                let len = env.pop(3)?.as_nat(env, "Length must be a natural number")?;
+                let bytes = env
+                    .backend
+                    .file_read_range(&path, offset, len)
+                    .map_err(|e| env.error(e))?;
+                env.push(Array::<u8>::from_iter(bytes));
+            }
+            SysOp::FAppend => {
+                let path = env.pop(1)?.as_string(env, "Path must be a string")?;
+                let bytes = value_to_file_bytes(env.pop(2)?, env)?;
+                env.backend
+                    .file_append(&path, &bytes)
+                    .map_err(|e| env.error(e))?;
+            }
             SysOp::FWriteAll => {
                 let path = env.pop(1)?.as_string(env, "Path must be a string")?;
-                let data = env.pop(2)?;
-                let bytes: Vec<u8> = match data {
-                    Value::Num(arr) => arr.data.iter().map(|&x| x as u8).collect(),
-                    Value::Byte(arr) => arr.data.into(),
-                    Value::Char(arr) => arr.data.iter().collect::<String>().into(),
-                    Value::Func(_) => return Err(env.error("Cannot write function array to file")),
-                };
+                let bytes = value_to_file_bytes(env.pop(2)?, env)?;
                 env.backend
                     .file_write_all(&path, &bytes)
                     .or_else(|e| {
@@ -1358,6 +1939,13 @@ impl SysOp {
                 let is_file = env.backend.is_file(&path).map_err(|e| env.error(e))?;
                 env.push(is_file);
             }
+            SysOp::FWatch => {
+                let path = env.pop(1)?.as_string(env, "Path must be a string")?;
+                let interrupt = env.interrupt_handle();
+                let watched = env.backend.watch_file(&path, &interrupt);
+                watched.map_err(|e| env.error(e))?;
+                env.check_interrupt()?;
+            }
             SysOp::Import => {
                 let path = env.pop(1)?.as_string(env, "Import path must be a string")?;
                 let input = String::from_utf8(
@@ -1433,6 +2021,19 @@ impl SysOp {
                 let image = value_to_image(&value).map_err(|e| env.error(e))?;
                 env.backend.show_image(image).map_err(|e| env.error(e))?;
             }
+            SysOp::ImWrite => {
+                let path = env.pop(1)?.as_string(env, "Path must be a string")?;
+                let value = env.pop(2)?;
+                let image = value_to_written_image(&value).map_err(|e| env.error(e))?;
+                env.backend
+                    .write_image(&path, image)
+                    .map_err(|e| env.error(e))?;
+            }
+            SysOp::ImRead => {
+                let path = env.pop(1)?.as_string(env, "Path must be a string")?;
+                let image = env.backend.read_image(&path).map_err(|e| env.error(e))?;
+                env.push(image_to_array(image));
+            }
             SysOp::GifEncode => {
                 let delay = env.pop(1)?.as_num(env, "Delay must be a number")?;
                 let value = env.pop(2)?;
@@ -1522,6 +2123,16 @@ impl SysOp {
                     return Err(env.error(e));
                 }
             }
+            SysOp::Exit => {
+                let status = env
+                    .pop(1)?
+                    .as_nat(env, "Exit status must be a natural number")?;
+                let status = i32::try_from(status)
+                    .map_err(|_| env.error(format!("Exit status {status} is too large")))?;
+                env.backend.exit(status).map_err(|e| env.error(e))?;
+                // The backend did not end the process
+                return Err(UiuaError::Exit(status, env.span().clone()));
+            }
             SysOp::Sleep => {
                 let seconds = env
                     .pop(1)?
@@ -1529,6 +2140,37 @@ impl SysOp {
                     .max(0.0);
                 env.backend.sleep(seconds).map_err(|e| env.error(e))?;
             }
+            SysOp::CpuTime => {
+                let time = env.backend.cpu_time().map_err(|e| env.error(e))?;
+                env.push(time);
+            }
+            SysOp::SystemMemory => {
+                let (total, available) = env.backend.system_memory().map_err(|e| env.error(e))?;
+                env.push(Value::from(vec![total as f64, available as f64]));
+            }
+            SysOp::BackendName => {
+                let name = env.backend.name().to_string();
+                env.push(name);
+            }
+            SysOp::TimerStart => {
+                let handle = env.backend.timer_start().map_err(|e| env.error(e))?;
+                env.push(handle);
+            }
+            SysOp::TimerElapsed => {
+                let handle = env
+                    .pop(1)?
+                    .as_nat(env, "Handle must be an natural number")?
+                    .into();
+                let seconds = env
+                    .backend
+                    .timer_elapsed(handle)
+                    .map_err(|e| env.error(e))?;
+                env.push(seconds);
+            }
+            SysOp::FrameDelta => {
+                let seconds = env.backend.frame_delta().map_err(|e| env.error(e))?;
+                env.push(seconds);
+            }
             SysOp::TcpListen => {
                 let addr = env.pop(1)?.as_string(env, "Address must be a string")?;
                 let handle = env.backend.tcp_listen(&addr).map_err(|e| env.error(e))?;
@@ -1608,6 +2250,79 @@ impl SysOp {
                     .map_err(|e| env.error(e))?;
                 env.push(res);
             }
+            #[cfg(feature = "json")]
+            SysOp::JsonDecode => {
+                let json = env.pop(1)?.as_string(env, "JSON must be a string")?;
+                let value = Value::from_json_string(&json, env)?;
+                env.push(value);
+            }
+            #[cfg(feature = "json")]
+            SysOp::JsonEncode => {
+                let value = env.pop(1)?;
+                let json = value.to_json_string(env)?;
+                env.push(json);
+            }
+            #[cfg(not(feature = "json"))]
+            SysOp::JsonDecode | SysOp::JsonEncode => {
+                return Err(env.error("JSON support is not enabled in this build"))
+            }
+            #[cfg(feature = "hash")]
+            SysOp::Hash => {
+                use sha2::{Digest, Sha256};
+                let bytes = env
+                    .pop(1)?
+                    .into_bytes(env, "Hashed value must be a string or byte array")?;
+                let digest = Sha256::digest(bytes);
+                env.push(Array::<u8>::from(digest.to_vec()));
+            }
+            #[cfg(not(feature = "hash"))]
+            SysOp::Hash => return Err(env.error("Hashing is not enabled in this build")),
+            SysOp::SecureRandom => {
+                let n = env
+                    .pop(1)?
+                    .as_nat(env, "Byte count must be a natural number")?;
+                let bytes = env.backend.secure_random(n).map_err(|e| env.error(e))?;
+                env.push(Array::<u8>::from(bytes));
+            }
+            #[cfg(feature = "base64")]
+            SysOp::Base64Encode => {
+                use base64::{engine::general_purpose::STANDARD, Engine};
+                let bytes = env
+                    .pop(1)?
+                    .into_bytes(env, "Base64 encoded value must be a string or byte array")?;
+                env.push(STANDARD.encode(bytes));
+            }
+            #[cfg(feature = "base64")]
+            SysOp::Base64Decode => {
+                use base64::{engine::general_purpose::STANDARD, DecodeError, Engine};
+                let encoded = env.pop(1)?.as_string(env, "Base64 must be a string")?;
+                let bytes = STANDARD.decode(&encoded).map_err(|e| {
+                    env.error(match e {
+                        DecodeError::InvalidByte(i, b) | DecodeError::InvalidLastSymbol(i, b) => {
+                            format!("Invalid base64 character {:?} at position {i}", b as char)
+                        }
+                        DecodeError::InvalidLength => {
+                            format!("Invalid base64 length {}", encoded.len())
+                        }
+                        DecodeError::InvalidPadding => "Invalid base64 padding".into(),
+                    })
+                })?;
+                env.push(Array::<u8>::from(bytes));
+            }
+            SysOp::CsvDecode => {
+                let csv = env.pop(1)?.as_string(env, "CSV must be a string")?;
+                let value = Value::from_csv_string(&csv, env)?;
+                env.push(value);
+            }
+            SysOp::CsvEncode => {
+                let value = env.pop(1)?;
+                let csv = value.to_csv_string(env)?;
+                env.push(csv);
+            }
+            #[cfg(not(feature = "base64"))]
+            SysOp::Base64Encode | SysOp::Base64Decode => {
+                return Err(env.error("Base64 support is not enabled in this build"))
+            }
             SysOp::Close => {
                 let handle = env
                     .pop(1)?
@@ -1690,7 +2405,7 @@ fn value_to_command(value: &Value, env: &Uiua) -> UiuaResult<(String, Vec<String
                 )))
             }
         },
-        Value::Num(_) | Value::Byte(_) => {
+        Value::Num(_) | Value::Byte(_) | Value::Complex(_) => {
             return Err(env.error(format!(
                 "Command must be a string or function array, but it is {}s",
                 value.type_name()
@@ -1704,6 +2419,16 @@ fn value_to_command(value: &Value, env: &Uiua) -> UiuaResult<(String, Vec<String
     Ok((command, strings))
 }
 
+fn value_to_file_bytes(value: Value, env: &Uiua) -> UiuaResult<Vec<u8>> {
+    Ok(match value {
+        Value::Num(arr) => arr.data.iter().map(|&x| x as u8).collect(),
+        Value::Byte(arr) => arr.data.into(),
+        Value::Complex(_) => return Err(env.error("Cannot write complex array to file")),
+        Value::Char(arr) => arr.data.iter().collect::<String>().into(),
+        Value::Func(_) => return Err(env.error("Cannot write function array to file")),
+    })
+}
+
 pub fn value_to_image_bytes(value: &Value, format: ImageOutputFormat) -> Result<Vec<u8>, String> {
     image_to_bytes(&value_to_image(value)?, format)
 }
@@ -1756,6 +2481,69 @@ pub fn value_to_image(value: &Value) -> Result<DynamicImage, String> {
     })
 }
 
+/// Convert an image to an array, keeping its channel count
+///
+/// Grayscale images are rank 2. All other images are rank 3.
+fn image_to_array(image: DynamicImage) -> Array<f64> {
+    let (width, height) = (image.width() as usize, image.height() as usize);
+    let (channels, bytes) = match image.color().channel_count() {
+        1 => (1, image.into_luma8().into_raw()),
+        2 => (2, image.into_luma_alpha8().into_raw()),
+        3 => (3, image.into_rgb8().into_raw()),
+        _ => (4, image.into_rgba8().into_raw()),
+    };
+    let shape = if channels == 1 {
+        tiny_vec![height, width]
+    } else {
+        tiny_vec![height, width, channels]
+    };
+    let data: CowSlice<f64> = bytes.into_iter().map(|b| b as f64 / 255.0).collect();
+    Array::new(shape, data)
+}
+
+/// Convert a value to an image that can be written to a file
+///
+/// Unlike [`value_to_image`], values above `1` mean that the
+/// image uses the `0` to `255` range.
+fn value_to_written_image(value: &Value) -> Result<DynamicImage, String> {
+    let (height, width, px_size) = match *value.shape() {
+        [h, w] => (h, w, 1),
+        [h, w, c @ (3 | 4)] => (h, w, c),
+        _ => {
+            return Err(format!(
+                "Image to write must have shape [h × w], [h × w × 3], or [h × w × 4], \
+                but its shape is {}",
+                value.format_shape()
+            ))
+        }
+    };
+    let bytes: Vec<u8> = match value {
+        Value::Num(nums) if nums.data.iter().all(|&n| n <= 1.0) => nums
+            .data
+            .iter()
+            .map(|&n| (n * 255.0).round() as u8)
+            .collect(),
+        Value::Num(nums) => nums.data.iter().map(|&n| n.round() as u8).collect(),
+        Value::Byte(bytes) if bytes.data.iter().all(|&b| b <= 1) => {
+            bytes.data.iter().map(|&b| b * 255).collect()
+        }
+        Value::Byte(bytes) => bytes.data.to_vec(),
+        _ => return Err("Image to write must be a numeric array".into()),
+    };
+    let (width, height) = (width as u32, height as u32);
+    Ok(match px_size {
+        1 => image::GrayImage::from_raw(width, height, bytes)
+            .ok_or("Failed to create image")?
+            .into(),
+        3 => image::RgbImage::from_raw(width, height, bytes)
+            .ok_or("Failed to create image")?
+            .into(),
+        _ => image::RgbaImage::from_raw(width, height, bytes)
+            .ok_or("Failed to create image")?
+            .into(),
+    })
+}
+
 pub fn value_to_sample(audio: &Value) -> Result<Vec<[f32; 2]>, String> {
     let unrolled: Vec<f32> = match audio {
         Value::Num(nums) => nums.data.iter().map(|&f| f as f32).collect(),
@@ -1986,3 +2774,265 @@ pub fn value_to_gif_bytes(value: &Value, frame_rate: f64) -> Result<Vec<u8>, Str
     drop(encoder);
     Ok(bytes.into_inner())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A temporary file path that is unique to this test process
+    fn temp_path(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("uiua_{}_{name}", std::process::id()))
+    }
+
+    #[cfg(feature = "hash")]
+    #[test]
+    fn image_write() {
+        let path = temp_path("image_write_test.png");
+        let path_str = path.to_string_lossy().replace('\\', "/");
+        let mut env = Uiua::with_native_sys();
+        env.load_str(&format!("&imgwrite {path_str:?} ↯2_3_3 [0 128 255]"))
+            .unwrap();
+        let image = image::open(&path).unwrap().into_rgb8();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!((image.width(), image.height()), (3, 2));
+        assert_eq!(image.get_pixel(0, 0).0, [0, 128, 255]);
+        let err = env
+            .load_str(&format!("&imgwrite {path_str:?} ↯2_3_2 0"))
+            .unwrap_err();
+        assert!(err.to_string().contains("[2 × 3 × 2]"), "{err}");
+    }
+
+    #[test]
+    fn image_read() {
+        let path = temp_path("image_read_test.png");
+        let path_str = path.to_string_lossy().replace('\\', "/");
+        let mut env = Uiua::with_native_sys();
+        let mut read = |write: &str| {
+            env.load_str(&format!("&imgwrite {path_str:?} {write}"))
+                .unwrap();
+            env.load_str(&format!("&imgread {path_str:?}")).unwrap();
+            env.pop("image").unwrap()
+        };
+        let image = read("↯2_3_3 [0 51 255]");
+        assert_eq!(image.shape(), &[2, 3, 3]);
+        assert_eq!(image.row(0).row(0), Value::from(vec![0.0, 0.2, 1.0]));
+        assert_eq!(read("↯2_3 0").shape(), &[2, 3]);
+        std::fs::write(&path, "not an image").unwrap();
+        let err = env.load_str(&format!("&imgread {path_str:?}")).unwrap_err();
+        let _ = std::fs::remove_file(&path);
+        assert!(err.to_string().contains("Failed to read image"), "{err}");
+    }
+
+    #[test]
+    fn system_memory() {
+        let mut env = Uiua::with_native_sys();
+        env.load_str("&sysmem").unwrap();
+        let memory = env.pop("memory").unwrap();
+        assert_eq!(memory.shape(), &[2]);
+        let [total, available] = [0, 1].map(|i| memory.row(i).as_num(&env, "").unwrap());
+        assert!(total > 0.0);
+        assert!((0.0..=total).contains(&available), "{available} of {total}");
+    }
+
+    #[test]
+    fn frame_delta() {
+        let mut env = Uiua::with_native_sys();
+        env.load_str("&dt &sl 0.05 &dt").unwrap();
+        let second: f64 = env.pop_typed("second delta").unwrap();
+        let first: f64 = env.pop_typed("first delta").unwrap();
+        assert_eq!(first, 0.0);
+        assert!((0.05..1.0).contains(&second), "{second}");
+    }
+
+    #[test]
+    fn timer() {
+        let mut env = Uiua::with_native_sys();
+        env.load_str("&elapsed ⊙(&sl 0.05) &timer").unwrap();
+        let elapsed: f64 = env.pop_typed("elapsed").unwrap();
+        assert!((0.05..1.0).contains(&elapsed), "{elapsed}");
+        let err = env.load_str("&elapsed ⊙&cl . &timer").unwrap_err();
+        assert!(err.to_string().contains("Invalid timer handle"), "{err}");
+    }
+
+    #[test]
+    fn file_read_range() {
+        let path = temp_path("file_read_range_test.txt");
+        std::fs::write(&path, "Hello, World!").unwrap();
+        let path_str = path.to_string_lossy().replace('\\', "/");
+        let mut env = Uiua::with_native_sys();
+        let read = |env: &mut Uiua, offset: usize, len: usize| {
+            env.load_str(&format!("&fread {path_str:?} {offset} {len}"))
+                .unwrap();
+            env.pop("bytes").unwrap()
+        };
+        assert_eq!(read(&mut env, 7, 5), Value::from(Vec::from(*b"World")));
+        assert_eq!(read(&mut env, 7, 100), Value::from(Vec::from(*b"World!")));
+        assert_eq!(read(&mut env, 100, 5), Value::from(Vec::<u8>::new()));
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn file_append() {
+        let path = temp_path("file_append_test.txt");
+        let _ = std::fs::remove_file(&path);
+        let path_str = path.to_string_lossy().replace('\\', "/");
+        let mut env = Uiua::with_native_sys();
+        env.load_str(&format!("&fappend {path_str:?} \"Hello\""))
+            .unwrap();
+        env.load_str(&format!("&fappend {path_str:?} \", World!\""))
+            .unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "Hello, World!");
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[cfg(feature = "getrandom")]
+    #[test]
+    fn secure_random() {
+        let mut env = Uiua::with_native_sys();
+        env.load_str("&csrand 32 &csrand 32").unwrap();
+        let a = env.pop("first bytes").unwrap();
+        let b = env.pop("second bytes").unwrap();
+        assert!(matches!(a, Value::Byte(_)));
+        assert_eq!(a.shape(), &[32]);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn progress_reports() {
+        #[derive(Default)]
+        struct ProgressSys {
+            reports: Mutex<Vec<(usize, usize)>>,
+        }
+        impl SysBackend for ProgressSys {
+            fn any(&self) -> &dyn Any {
+                self
+            }
+            fn reports_progress(&self) -> bool {
+                true
+            }
+            fn report_progress(&self, done: usize, total: usize) {
+                self.reports.lock().push((done, total));
+            }
+        }
+
+        let mut env = Uiua::with_backend(ProgressSys::default());
+        env.load_str("∵(+1) ⇡2500\n≡(×2) ⇡10").unwrap();
+        let reports = env
+            .downcast_backend::<ProgressSys>()
+            .unwrap()
+            .reports
+            .lock();
+        assert_eq!(
+            *reports,
+            [(1000, 2500), (2000, 2500), (2500, 2500), (10, 10)]
+        );
+    }
+
+    #[test]
+    fn backend_name() {
+        let mut env = Uiua::with_native_sys();
+        env.load_str("&backend").unwrap();
+        let name = env.pop("name").unwrap().as_string(&env, "").unwrap();
+        assert_eq!(name, "native");
+
+        struct EmbeddedSys;
+        impl SysBackend for EmbeddedSys {
+            fn any(&self) -> &dyn Any {
+                self
+            }
+        }
+
+        let mut env = Uiua::with_backend(EmbeddedSys);
+        env.load_str("&backend").unwrap();
+        let name = env.pop("name").unwrap().as_string(&env, "").unwrap();
+        assert_eq!(name, "custom");
+    }
+
+    #[test]
+    fn exit_without_ending_process() {
+        struct EmbeddedSys;
+        impl SysBackend for EmbeddedSys {
+            fn any(&self) -> &dyn Any {
+                self
+            }
+        }
+
+        let mut env = Uiua::with_backend(EmbeddedSys);
+        let err = env.load_str("&exit 3").unwrap_err();
+        assert_eq!(err.exit_status(), Some(3));
+        assert!(err.to_string().contains("exited with status 3"), "{err}");
+        let err = env.load_str("&exit ¯1").unwrap_err();
+        assert_eq!(err.exit_status(), None);
+        // The native backend leaves ending the process to the host too
+        let err = Uiua::with_native_sys().load_str("&exit 4").unwrap_err();
+        assert_eq!(err.exit_status(), Some(4));
+    }
+
+    #[test]
+    fn native_sys_base_dir() {
+        let dir = env::temp_dir().join(format!("uiua-base-dir-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("hello.txt"), "Hello, World!").unwrap();
+
+        let mut env = Uiua::with_backend(NativeSys::with_base_dir(&dir));
+        env.load_str(r#"&fras "hello.txt""#).unwrap();
+        let contents = env.pop("contents").unwrap();
+        assert_eq!(contents.as_string(&env, "").unwrap(), "Hello, World!");
+
+        let mut env = Uiua::with_backend(NativeSys::with_base_dir(&dir));
+        assert!(env.load_str(r#"&fras "../hello.txt""#).is_err());
+        let absolute = dir.join("hello.txt").to_string_lossy().into_owned();
+        assert!(env.load_str(&format!("&fras {absolute:?}")).is_err());
+
+        let mut env =
+            Uiua::with_backend(NativeSys::with_base_dir(&dir).allow_outside_base_dir(true));
+        env.load_str(&format!("&fras {absolute:?}")).unwrap();
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn file_watch() {
+        let path = temp_path("file_watch_test.txt");
+        let path_str = path.to_string_lossy().replace('\\', "/");
+        std::fs::write(&path, "before").unwrap();
+        let writer = {
+            let path = path.clone();
+            spawn(move || {
+                sleep(Duration::from_millis(300));
+                std::fs::write(path, "after").unwrap();
+            })
+        };
+        let mut env = Uiua::with_native_sys();
+        let start = std::time::Instant::now();
+        env.load_str(&format!("&fwatch {path_str:?}")).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(250));
+        writer.join().unwrap();
+        // Interrupting stops the watch
+        let interrupt = env.interrupt_handle();
+        let interrupter = spawn(move || {
+            sleep(Duration::from_millis(200));
+            interrupt.store(true, atomic::Ordering::Relaxed);
+        });
+        let err = env.load_str(&format!("&fwatch {path_str:?}")).unwrap_err();
+        interrupter.join().unwrap();
+        assert!(err.to_string().contains("interrupted"), "{err}");
+        env.interrupt_handle()
+            .store(false, atomic::Ordering::Relaxed);
+        let _ = std::fs::remove_file(&path);
+        let err = env.load_str(&format!("&fwatch {path_str:?}")).unwrap_err();
+        assert!(err.to_string().contains("Cannot watch"), "{err}");
+    }
+
+    #[test]
+    fn is_tty() {
+        let mut env = Uiua::with_native_sys();
+        for stream in 0..3 {
+            env.load_str(&format!("&isatty {stream}")).unwrap();
+            let is_tty = env.pop("is tty").unwrap();
+            assert!(is_tty == Value::from(0u8) || is_tty == Value::from(1u8));
+        }
+        let err = env.load_str("&isatty 3").unwrap_err();
+        assert!(err.to_string().contains("0, 1, or 2"), "{err}");
+    }
+}
@@ -10,8 +10,9 @@ use std::{
 use crate::{
     algorithm::{pervade::*, FillContext},
     array::*,
+    complex::Complex,
     function::{Function, Signature},
-    grid_fmt::GridFmt,
+    grid_fmt::{GridFmt, GridFmtParams},
     primitive::Primitive,
     Uiua, UiuaResult,
 };
@@ -20,6 +21,7 @@ use crate::{
 pub enum Value {
     Num(Array<f64>),
     Byte(Array<u8>),
+    Complex(Array<Complex>),
     Char(Array<char>),
     Func(Array<Arc<Function>>),
 }
@@ -35,6 +37,7 @@ impl fmt::Debug for Value {
         match self {
             Self::Num(array) => array.fmt(f),
             Self::Byte(array) => array.fmt(f),
+            Self::Complex(array) => array.fmt(f),
             Self::Char(array) => array.fmt(f),
             Self::Func(array) => array.fmt(f),
         }
@@ -97,6 +100,7 @@ impl Value {
         match self {
             Self::Num(array) => Box::new(array.rows().map(Value::from)),
             Self::Byte(array) => Box::new(array.rows().map(Value::from)),
+            Self::Complex(array) => Box::new(array.rows().map(Value::from)),
             Self::Char(array) => Box::new(array.rows().map(Value::from)),
             Self::Func(array) => Box::new(array.rows().map(Value::from)),
         }
@@ -105,6 +109,7 @@ impl Value {
         match self {
             Self::Num(array) => Box::new(array.into_rows().map(Value::from)),
             Self::Byte(array) => Box::new(array.into_rows().map(Value::from)),
+            Self::Complex(array) => Box::new(array.into_rows().map(Value::from)),
             Self::Char(array) => Box::new(array.into_rows().map(Value::from)),
             Self::Func(array) => Box::new(array.into_rows().map(Value::from)),
         }
@@ -113,6 +118,7 @@ impl Value {
         match self {
             Self::Num(array) => Box::new(array.into_rows_rev().map(Value::from)),
             Self::Byte(array) => Box::new(array.into_rows_rev().map(Value::from)),
+            Self::Complex(array) => Box::new(array.into_rows_rev().map(Value::from)),
             Self::Char(array) => Box::new(array.into_rows_rev().map(Value::from)),
             Self::Func(array) => Box::new(array.into_rows_rev().map(Value::from)),
         }
@@ -121,6 +127,7 @@ impl Value {
         match self {
             Self::Num(array) => Box::new(array.data.into_iter().map(Value::from)),
             Self::Byte(array) => Box::new(array.data.into_iter().map(Value::from)),
+            Self::Complex(array) => Box::new(array.data.into_iter().map(Value::from)),
             Self::Char(array) => Box::new(array.data.into_iter().map(Value::from)),
             Self::Func(array) => Box::new(array.data.into_iter().map(Value::from)),
         }
@@ -128,6 +135,7 @@ impl Value {
     pub fn type_name(&self) -> &'static str {
         match self {
             Self::Num(_) | Self::Byte(_) => "number",
+            Self::Complex(_) => "complex",
             Self::Char(_) => "character",
             Self::Func(_) => "function",
         }
@@ -136,6 +144,7 @@ impl Value {
         match self {
             Self::Num(array) => array.shape(),
             Self::Byte(array) => array.shape(),
+            Self::Complex(array) => array.shape(),
             Self::Char(array) => array.shape(),
             Self::Func(array) => array.shape(),
         }
@@ -149,6 +158,7 @@ impl Value {
             Array::row_count,
             Array::row_count,
             Array::row_count,
+            Array::row_count,
         )
     }
     pub fn row_len(&self) -> usize {
@@ -157,6 +167,7 @@ impl Value {
             Array::row_len,
             Array::row_len,
             Array::row_len,
+            Array::row_len,
         )
     }
     pub fn flat_len(&self) -> usize {
@@ -165,12 +176,30 @@ impl Value {
             Array::flat_len,
             Array::flat_len,
             Array::flat_len,
+            Array::flat_len,
         )
     }
+    /// Get the approximate number of bytes used by the value's elements
+    ///
+    /// Boxes and functions include the sizes of their contents.
+    pub fn mem_size(&self) -> usize {
+        match self {
+            Self::Num(array) => array.data.len() * size_of::<f64>(),
+            Self::Byte(array) => array.data.len() * size_of::<u8>(),
+            Self::Complex(array) => array.data.len() * size_of::<Complex>(),
+            Self::Char(array) => array.data.len() * size_of::<char>(),
+            Self::Func(array) => array
+                .data
+                .iter()
+                .map(|f| size_of::<Arc<Function>>() + f.mem_size())
+                .sum(),
+        }
+    }
     pub fn reserve_min(&mut self, min: usize) {
         match self {
             Self::Num(arr) => arr.data.reserve_min(min),
             Self::Byte(arr) => arr.data.reserve_min(min),
+            Self::Complex(arr) => arr.data.reserve_min(min),
             Self::Char(arr) => arr.data.reserve_min(min),
             Self::Func(arr) => arr.data.reserve_min(min),
         }
@@ -179,6 +208,7 @@ impl Value {
         match self {
             Self::Num(array) => array.first_dim_zero().into(),
             Self::Byte(array) => array.first_dim_zero().into(),
+            Self::Complex(array) => array.first_dim_zero().into(),
             Self::Char(array) => array.first_dim_zero().into(),
             Self::Func(array) => array.first_dim_zero().into(),
         }
@@ -189,6 +219,7 @@ impl Value {
             Array::format_shape,
             Array::format_shape,
             Array::format_shape,
+            Array::format_shape,
         )
     }
     pub fn rank(&self) -> usize {
@@ -198,6 +229,7 @@ impl Value {
         match self {
             Self::Num(array) => &mut array.shape,
             Self::Byte(array) => &mut array.shape,
+            Self::Complex(array) => &mut array.shape,
             Self::Char(array) => &mut array.shape,
             Self::Func(array) => &mut array.shape,
         }
@@ -208,6 +240,7 @@ impl Value {
             Array::validate_shape,
             Array::validate_shape,
             Array::validate_shape,
+            Array::validate_shape,
         )
     }
     pub fn row(&self, i: usize) -> Self {
@@ -216,21 +249,24 @@ impl Value {
             |arr| arr.row(i).into(),
             |arr| arr.row(i).into(),
             |arr| arr.row(i).into(),
+            |arr| arr.row(i).into(),
         )
     }
     pub fn generic_into<T>(
         self,
         n: impl FnOnce(Array<f64>) -> T,
         b: impl FnOnce(Array<u8>) -> T,
+        co: impl FnOnce(Array<Complex>) -> T,
         c: impl FnOnce(Array<char>) -> T,
         f: impl FnOnce(Array<Arc<Function>>) -> T,
     ) -> T {
         match self {
             Self::Num(array) => n(array),
             Self::Byte(array) => b(array),
+            Self::Complex(array) => co(array),
             Self::Char(array) => c(array),
             Self::Func(array) => match array.into_constant() {
-                Ok(value) => value.generic_into(n, b, c, f),
+                Ok(value) => value.generic_into(n, b, co, c, f),
                 Err(array) => f(array),
             },
         }
@@ -239,16 +275,18 @@ impl Value {
         &'a self,
         n: impl FnOnce(&'a Array<f64>) -> T,
         b: impl FnOnce(&'a Array<u8>) -> T,
+        co: impl FnOnce(&'a Array<Complex>) -> T,
         c: impl FnOnce(&'a Array<char>) -> T,
         f: impl FnOnce(&'a Array<Arc<Function>>) -> T,
     ) -> T {
         match self {
             Self::Num(array) => n(array),
             Self::Byte(array) => b(array),
+            Self::Complex(array) => co(array),
             Self::Char(array) => c(array),
             Self::Func(array) => {
                 if let Some(value) = array.as_constant() {
-                    value.generic_ref(n, b, c, f)
+                    value.generic_ref(n, b, co, c, f)
                 } else {
                     f(array)
                 }
@@ -259,26 +297,35 @@ impl Value {
         &'a self,
         n: impl FnOnce(&'a Array<f64>, &Uiua) -> UiuaResult<T>,
         b: impl FnOnce(&'a Array<u8>, &Uiua) -> UiuaResult<T>,
+        co: impl FnOnce(&'a Array<Complex>, &Uiua) -> UiuaResult<T>,
         c: impl FnOnce(&'a Array<char>, &Uiua) -> UiuaResult<T>,
         f: impl FnOnce(&'a Array<Arc<Function>>, &Uiua) -> UiuaResult<T>,
         env: &Uiua,
     ) -> UiuaResult<T> {
-        self.generic_ref(|a| n(a, env), |a| b(a, env), |a| c(a, env), |a| f(a, env))
+        self.generic_ref(
+            |a| n(a, env),
+            |a| b(a, env),
+            |a| co(a, env),
+            |a| c(a, env),
+            |a| f(a, env),
+        )
     }
     pub fn generic_mut<T>(
         &mut self,
         n: impl FnOnce(&mut Array<f64>) -> T,
         b: impl FnOnce(&mut Array<u8>) -> T,
+        co: impl FnOnce(&mut Array<Complex>) -> T,
         c: impl FnOnce(&mut Array<char>) -> T,
         f: impl FnOnce(&mut Array<Arc<Function>>) -> T,
     ) -> T {
         match self {
             Self::Num(array) => n(array),
             Self::Byte(array) => b(array),
+            Self::Complex(array) => co(array),
             Self::Char(array) => c(array),
             Self::Func(array) => {
                 if let Some(value) = array.as_constant_mut() {
-                    value.generic_mut(n, b, c, f)
+                    value.generic_mut(n, b, co, c, f)
                 } else {
                     f(array)
                 }
@@ -287,11 +334,23 @@ impl Value {
     }
     /// Get the pretty-printed string representation of the value
     pub fn show(&self) -> String {
+        self.show_with_precision(None)
+    }
+    /// Get the pretty-printed string representation of the value,
+    /// showing numbers with at most some number of significant digits
+    ///
+    /// Digits before the decimal point are never dropped.
+    pub fn show_with_precision(&self, precision: Option<usize>) -> String {
+        let params = GridFmtParams {
+            precision,
+            ..Default::default()
+        };
         match self {
-            Self::Num(array) => array.grid_string(),
-            Self::Byte(array) => array.grid_string(),
-            Self::Char(array) => array.grid_string(),
-            Self::Func(array) => array.grid_string(),
+            Self::Num(array) => array.grid_string_with(params),
+            Self::Byte(array) => array.grid_string_with(params),
+            Self::Complex(array) => array.grid_string_with(params),
+            Self::Char(array) => array.grid_string_with(params),
+            Self::Func(array) => array.grid_string_with(params),
         }
     }
     pub fn as_primitive(&self) -> Option<(Primitive, usize)> {
@@ -538,6 +597,32 @@ impl Value {
             )))
         }
     }
+    /// Get the numbers of a rank `0` or `1` number or byte array
+    pub fn as_num_vec(&self) -> Option<Vec<f64>> {
+        match self {
+            Value::Num(nums) if nums.rank() <= 1 => Some(nums.data.to_vec()),
+            Value::Byte(bytes) if bytes.rank() <= 1 => {
+                Some(bytes.data.iter().map(|&b| b as f64).collect())
+            }
+            _ => None,
+        }
+    }
+    /// Get the natural numbers of a rank `0` or `1` number or byte array
+    ///
+    /// Returns `None` if any of the numbers are negative or have a fractional part.
+    pub fn as_nat_vec(&self) -> Option<Vec<usize>> {
+        match self {
+            Value::Num(nums) if nums.rank() <= 1 => nums
+                .data
+                .iter()
+                .map(|&n| (n.fract() == 0.0 && n >= 0.0).then_some(n as usize))
+                .collect(),
+            Value::Byte(bytes) if bytes.rank() <= 1 => {
+                Some(bytes.data.iter().map(|&b| b as usize).collect())
+            }
+            _ => None,
+        }
+    }
     pub fn into_bytes(self, env: &Uiua, requirement: &'static str) -> UiuaResult<Vec<u8>> {
         Ok(match self {
             Value::Byte(a) => {
@@ -586,6 +671,7 @@ impl Value {
         match self {
             Value::Num(arr) => arr.convert_with(|n| Arc::new(Function::constant(n))),
             Value::Byte(arr) => arr.convert_with(|n| Arc::new(Function::constant(n))),
+            Value::Complex(arr) => arr.convert_with(|n| Arc::new(Function::constant(n))),
             Value::Char(arr) => arr.convert_with(|n| Arc::new(Function::constant(n))),
             Value::Func(arr) => arr,
         }
@@ -598,6 +684,9 @@ impl Value {
             Value::Byte(arr) => {
                 Cow::Owned(arr.convert_ref_with(|n| Arc::new(Function::constant(n))))
             }
+            Value::Complex(arr) => {
+                Cow::Owned(arr.convert_ref_with(|n| Arc::new(Function::constant(n))))
+            }
             Value::Char(arr) => {
                 Cow::Owned(arr.convert_ref_with(|n| Arc::new(Function::constant(n))))
             }
@@ -638,6 +727,7 @@ macro_rules! value_from {
 
 value_from!(f64, Num);
 value_from!(u8, Byte);
+value_from!(Complex, Complex);
 value_from!(char, Char);
 value_from!(Arc<Function>, Func);
 
@@ -647,6 +737,94 @@ impl FromIterator<usize> for Value {
     }
 }
 
+/// A builder for making a [`Value`] one row at a time
+///
+/// Rows are appended in place, so building an array of `n` rows takes `O(n)` time,
+/// unlike repeatedly joining.
+#[derive(Debug, Clone, Default)]
+pub struct ArrayBuilder {
+    value: Option<Value>,
+}
+
+impl ArrayBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Append a row
+    ///
+    /// The row must have the same shape as the others unless there is a fill value.
+    pub fn push_row(&mut self, row: impl Into<Value>, env: &Uiua) -> UiuaResult {
+        let row = row.into();
+        let Some(value) = &mut self.value else {
+            let mut value = row;
+            value.shape_mut().insert(0, 1);
+            self.value = Some(value);
+            return Ok(());
+        };
+        match (value, row) {
+            // Appending bytes may need to retry as numbers,
+            // which would copy the array every time
+            (Value::Byte(a), Value::Byte(b)) if a.shape()[1..] == *b.shape() => a.append(b, env),
+            (value, row) => value.append(row, env),
+        }
+    }
+    /// Get the number of rows appended so far
+    pub fn len(&self) -> usize {
+        self.value.as_ref().map_or(0, Value::row_count)
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Get the built array
+    ///
+    /// If no rows were appended, this is an empty list.
+    pub fn finish(self) -> Value {
+        self.value.unwrap_or_default()
+    }
+}
+
+/// A type that can be extracted from a [`Value`]
+///
+/// This is used by [`Uiua::pop_typed`].
+pub trait FromValue: Sized {
+    /// Convert a value, erroring if it has the wrong type or shape
+    fn from_value(value: Value, env: &Uiua) -> UiuaResult<Self>;
+}
+
+impl FromValue for Value {
+    fn from_value(value: Value, _env: &Uiua) -> UiuaResult<Self> {
+        Ok(value)
+    }
+}
+
+macro_rules! from_value {
+    ($($ty:ty, $method:ident, $requirement:literal);* $(;)?) => {
+        $(
+            impl FromValue for $ty {
+                fn from_value(value: Value, env: &Uiua) -> UiuaResult<Self> {
+                    value.$method(env, $requirement)
+                }
+            }
+        )*
+    };
+}
+
+from_value!(
+    bool, as_bool, "Value should be a boolean";
+    usize, as_nat, "Value should be a natural number";
+    isize, as_int, "Value should be an integer";
+    f64, as_num, "Value should be a number";
+    String, as_string, "Value should be a string";
+    Vec<usize>, as_naturals, "Value should be a list of natural numbers";
+    Vec<isize>, as_integers, "Value should be a list of integers";
+);
+
+impl FromValue for Vec<f64> {
+    fn from_value(value: Value, env: &Uiua) -> UiuaResult<Self> {
+        value.as_number_list(env, "Value should be a list of numbers", |_| true, |f| f)
+    }
+}
+
 impl From<bool> for Value {
     fn from(b: bool) -> Self {
         Value::from(b as u8)
@@ -715,7 +893,49 @@ macro_rules! value_un_impl_all {
     }
 }
 
-value_un_impl_all!(neg, not, abs, sign, sqrt, sin, cos, tan, asin, acos, floor, ceil, round);
+value_un_impl!(neg, (Num, num), (Byte, byte), (Complex, com));
+value_un_impl!(abs, (Num, num), (Byte, byte), (Complex, com));
+value_un_impl!(sqrt, (Num, num), (Byte, byte), (Complex, com));
+value_un_impl_all!(
+    sign, sin, cos, sin_deg, cos_deg, tan, asin, acos, floor, ceil, trunc, round_away, round
+);
+value_un_impl_all!(is_nan, is_infinite);
+
+impl Value {
+    pub fn not(self, env: &Uiua) -> UiuaResult<Self> {
+        Ok(match self {
+            // Boolean masks stay bytes
+            Value::Byte(mut array) if array.data.iter().all(|&b| b <= 1) => {
+                for b in array.data.iter_mut() {
+                    *b = not::bool(*b);
+                }
+                array.into()
+            }
+            Value::Byte(array) => (
+                array.shape,
+                array.data.into_iter().map(not::byte).collect::<Vec<_>>(),
+            )
+                .into(),
+            Value::Num(array) => (
+                array.shape,
+                array.data.into_iter().map(not::num).collect::<Vec<_>>(),
+            )
+                .into(),
+            Value::Func(mut array) => {
+                let mut new_data = Vec::with_capacity(array.flat_len());
+                for f in array.data {
+                    match Function::into_inner(f).into_constant() {
+                        Ok(value) => new_data.push(Arc::new(Function::constant(value.not(env)?))),
+                        Err(_) => return Err(not::error("function", env)),
+                    }
+                }
+                array.data = new_data.into();
+                array.into()
+            }
+            val => return Err(not::error(val.type_name(), env)),
+        })
+    }
+}
 
 macro_rules! val_retry {
     (Byte, $env:expr) => {
@@ -726,6 +946,21 @@ macro_rules! val_retry {
     };
 }
 
+/// Pervade a dyadic operation over arrays of boxes, applying it to their contents
+fn bin_pervade_boxes(
+    a: &Array<Arc<Function>>,
+    b: &Array<Arc<Function>>,
+    env: &Uiua,
+    f: fn(&Value, &Value, &Uiua) -> UiuaResult<Value>,
+) -> UiuaResult<Value> {
+    let f = move |a: Arc<Function>, b: Arc<Function>, env: &Uiua| {
+        let a = (a.as_constant()).ok_or_else(|| env.error("First argument is not a box"))?;
+        let b = (b.as_constant()).ok_or_else(|| env.error("Second argument is not a box"))?;
+        Ok(Arc::new(Function::constant(f(a, b, env)?)))
+    };
+    bin_pervade(a, b, env, FalliblePerasiveFn::new(f)).map(Into::into)
+}
+
 macro_rules! value_bin_impl {
     ($name:ident, $(($va:ident, $vb:ident, $f:ident $(, $retry:ident)?)),* $(,)?) => {
         impl Value {
@@ -744,32 +979,17 @@ macro_rules! value_bin_impl {
                             Err(e) => return Err(e),
                         }
                     },)*
-                    (Value::Func(a), b) => {
-                        match a.as_constant() {
-                            Some(a) => Value::$name(a, b, env)?,
-                            None => {
-                                let b = b.coerce_as_function();
-                                bin_pervade(a, &b, env, FalliblePerasiveFn::new(|a: Arc<Function>, b: Arc<Function>, env: &Uiua| {
-                                    let a = a.as_constant().ok_or_else(|| env.error("First argument is not a box"))?;
-                                    let b = b.as_constant().ok_or_else(|| env.error("Second argument is not a box"))?;
-                                    Ok(Arc::new(Function::constant(Value::$name(a, b, env)?)))
-                                }))?.into()
-                            }
-                        }
+                    (Value::Func(a), b) => match a.as_constant() {
+                        Some(a) => Value::$name(a, b, env)?,
+                        None => bin_pervade_boxes(a, &b.coerce_as_function(), env, Value::$name)?,
                     },
-                    (a, Value::Func(b)) => {
-                        match b.as_constant() {
-                            Some(b) => Value::$name(a, b, env)?,
-                            None => {
-                                let a = a.coerce_as_function();
-                                bin_pervade(&a, b, env, FalliblePerasiveFn::new(|a: Arc<Function>, b: Arc<Function>, env: &Uiua| {
-                                    let a = a.as_constant().ok_or_else(|| env.error("First argument is not a box"))?;
-                                    let b = b.as_constant().ok_or_else(|| env.error("Second argument is not a box"))?;
-                                    Ok(Arc::new(Function::constant(Value::$name(a, b, env)?)))
-                                }))?.into()
-                            }
-                        }
+                    (a, Value::Func(b)) => match b.as_constant() {
+                        Some(b) => Value::$name(a, b, env)?,
+                        None => bin_pervade_boxes(&a.coerce_as_function(), b, env, Value::$name)?,
                     },
+                    // Bytes are numbers to operations without byte-specific implementations
+                    (Value::Byte(a), b) => Value::$name(&Value::Num(a.convert_ref()), b, env)?,
+                    (a, Value::Byte(b)) => Value::$name(a, &Value::Num(b.convert_ref()), env)?,
                     (a, b) => return Err($name::error(a.type_name(), b.type_name(), env)),
                 })
             }
@@ -787,6 +1007,9 @@ value_bin_impl!(
     (Char, Byte, char_byte),
     (Byte, Num, byte_num, num_num),
     (Num, Byte, num_byte, num_num),
+    (Num, Complex, num_com),
+    (Complex, Num, com_num),
+    (Complex, Complex, com_com),
 );
 
 value_bin_impl!(
@@ -798,6 +1021,9 @@ value_bin_impl!(
     (Byte, Char, byte_char),
     (Byte, Num, byte_num, num_num),
     (Num, Byte, num_byte, num_num),
+    (Num, Complex, num_com),
+    (Complex, Num, com_num),
+    (Complex, Complex, com_com),
 );
 
 value_bin_impl!(
@@ -806,6 +1032,9 @@ value_bin_impl!(
     (Byte, Byte, byte_byte, num_num),
     (Byte, Num, byte_num, num_num),
     (Num, Byte, num_byte, num_num),
+    (Num, Complex, num_com),
+    (Complex, Num, com_num),
+    (Complex, Complex, com_com),
 );
 value_bin_impl!(
     div,
@@ -813,6 +1042,9 @@ value_bin_impl!(
     (Byte, Byte, byte_byte, num_num),
     (Byte, Num, byte_num, num_num),
     (Num, Byte, num_byte, num_num),
+    (Num, Complex, num_com),
+    (Complex, Num, com_num),
+    (Complex, Complex, com_com),
 );
 value_bin_impl!(
     modulus,
@@ -827,6 +1059,9 @@ value_bin_impl!(
     (Byte, Byte, byte_byte, num_num),
     (Byte, Num, byte_num, num_num),
     (Num, Byte, num_byte, num_num),
+    (Num, Complex, num_com),
+    (Complex, Num, com_num),
+    (Complex, Complex, com_com),
 );
 value_bin_impl!(
     log,
@@ -836,6 +1071,65 @@ value_bin_impl!(
     (Num, Byte, num_byte, num_num),
 );
 value_bin_impl!(atan2, (Num, Num, num_num));
+value_bin_impl!(atan2_deg, (Num, Num, num_num));
+value_bin_impl!(
+    round_to,
+    (Num, Num, num_num),
+    (Byte, Byte, byte_byte, num_num),
+    (Byte, Num, byte_num, num_num),
+    (Num, Byte, num_byte, num_num),
+);
+
+value_bin_impl!(
+    add_sat,
+    (Num, Num, num_num),
+    (Byte, Byte, byte_byte, num_num),
+    (Byte, Num, byte_num, num_num),
+    (Num, Byte, num_byte, num_num),
+);
+
+value_bin_impl!(
+    sub_sat,
+    (Num, Num, num_num),
+    (Byte, Byte, byte_byte, num_num),
+    (Byte, Num, byte_num, num_num),
+    (Num, Byte, num_byte, num_num),
+);
+
+value_bin_impl!(
+    mul_sat,
+    (Num, Num, num_num),
+    (Byte, Byte, byte_byte, num_num),
+    (Byte, Num, byte_num, num_num),
+    (Num, Byte, num_byte, num_num),
+);
+
+value_bin_impl!(
+    gcd,
+    (Num, Num, num_num),
+    (Byte, Byte, byte_byte, num_num),
+    (Byte, Num, byte_num, num_num),
+    (Num, Byte, num_byte, num_num),
+);
+
+value_bin_impl!(
+    lcm,
+    (Num, Num, num_num),
+    (Byte, Byte, byte_byte, num_num),
+    (Byte, Num, byte_num, num_num),
+    (Num, Byte, num_byte, num_num),
+);
+
+value_bin_impl!(
+    complex,
+    (Num, Num, num_num),
+    (Byte, Byte, byte_byte, num_num),
+    (Byte, Num, byte_num, num_num),
+    (Num, Byte, num_byte, num_num),
+    (Num, Complex, num_com),
+    (Complex, Num, com_num),
+    (Complex, Complex, com_com),
+);
 
 value_bin_impl!(
     min,
@@ -867,11 +1161,16 @@ macro_rules! cmp_impls {
                 (Func, Func, generic),
                 (Num, Byte, num_byte, num_num),
                 (Byte, Num, byte_num, num_num),
+                (Complex, Complex, com_com),
+                (Num, Complex, num_com),
+                (Complex, Num, com_num),
                 // Type comparable
                 (Num, Char, always_less),
                 (Byte, Char, always_less),
                 (Char, Num, always_greater),
                 (Char, Byte, always_greater),
+                (Complex, Char, always_less),
+                (Char, Complex, always_greater),
             );
         )*
     };
@@ -885,6 +1184,7 @@ impl PartialEq for Value {
             (Value::Num(a), Value::Num(b)) => a == b,
             (Value::Byte(a), Value::Byte(b)) => a == b,
             (Value::Char(a), Value::Char(b)) => a == b,
+            (Value::Complex(a), Value::Complex(b)) => a == b,
             (Value::Func(a), Value::Func(b)) => a == b,
             (Value::Num(a), Value::Byte(b)) => a == b,
             (Value::Byte(a), Value::Num(b)) => a == b,
@@ -910,10 +1210,13 @@ impl Ord for Value {
             (Value::Func(a), Value::Func(b)) => a.cmp(b),
             (Value::Num(a), Value::Byte(b)) => a.partial_cmp(b).unwrap(),
             (Value::Byte(a), Value::Num(b)) => a.partial_cmp(b).unwrap(),
+            (Value::Complex(a), Value::Complex(b)) => a.cmp(b),
             (Value::Num(_), _) => Ordering::Less,
             (_, Value::Num(_)) => Ordering::Greater,
             (Value::Byte(_), _) => Ordering::Less,
             (_, Value::Byte(_)) => Ordering::Greater,
+            (Value::Complex(_), _) => Ordering::Less,
+            (_, Value::Complex(_)) => Ordering::Greater,
             (Value::Char(_), _) => Ordering::Less,
             (_, Value::Char(_)) => Ordering::Greater,
         }
@@ -939,6 +1242,10 @@ impl Hash for Value {
                 3u8.hash(state);
                 arr.hash(state);
             }
+            Value::Complex(arr) => {
+                4u8.hash(state);
+                arr.hash(state);
+            }
         }
     }
 }
@@ -948,6 +1255,7 @@ impl fmt::Display for Value {
         match self {
             Value::Num(n) => n.fmt(f),
             Value::Byte(b) => b.fmt(f),
+            Value::Complex(n) => n.fmt(f),
             Value::Char(c) => c.fmt(f),
             Value::Func(func) => {
                 if let Some(val) = func.as_constant() {